@@ -1,15 +1,14 @@
 // インポートを削除
 // use console_error_panic_hook;
 use log::Level;
-use serde::{Deserialize, Serialize};
 use serde_wasm_bindgen::{from_value, to_value};
 use std::panic;
 use wasm_bindgen::prelude::*;
 
 // 既存のモジュールをインポート
-mod models;
-mod services;
-mod utils;
+pub mod models;
+pub mod services;
+pub mod utils;
 
 // 初期化関数
 #[wasm_bindgen(start)]
@@ -26,28 +25,282 @@ pub fn start() {
 
 // モンスターデータをJavaScriptに公開
 #[wasm_bindgen]
-pub fn get_default_monsters() -> JsValue {
+pub fn get_default_monsters() -> Result<JsValue, JsValue> {
     // console_log::log!の代わりにweb_sysを使用
     web_sys::console::log_1(&"get_default_monsters".into());
     let monsters = models::monster::create_default_monsters().monsters;
     // 複雑なデータ構造はJSONに変換して出力
     web_sys::console::log_1(&format!("monsters count: {}", monsters.len()).into());
-    to_value(&monsters).unwrap()
+    to_js(&monsters)
 }
 
-// ダメージ計算関数をJavaScriptに公開
+// スキルデータをJavaScriptに公開
 #[wasm_bindgen]
-pub fn calculate_damage(monster_json: &JsValue, equipment_json: &JsValue) -> JsValue {
+pub fn get_default_skills() -> Result<JsValue, JsValue> {
+    let skills = models::skill::create_default_skills().skills;
+    to_js(&skills)
+}
+
+// 選択できる計算式のバージョンをJavaScriptに公開
+#[wasm_bindgen]
+pub fn get_formula_versions() -> Result<JsValue, JsValue> {
+    to_js(&models::formula::FORMULA_VERSIONS)
+}
+
+// ダメージ計算関数をJavaScriptに公開（計算式のバージョンは未指定なら現行、レベル差補正は未指定なら補正なし）
+#[wasm_bindgen]
+pub fn calculate_damage(
+    monster_json: &JsValue,
+    equipment_json: &JsValue,
+    character_json: &JsValue,
+    skill_json: &JsValue,
+    buffs_json: &JsValue,
+    formula_version: Option<String>,
+    level_correction_json: &JsValue,
+) -> Result<JsValue, JsValue> {
     // JavaScriptからのJSONをRustの型に変換
-    let monster: models::monster::Monster = from_value(monster_json.clone()).unwrap();
-    let equipment_set: models::equipment::EquipmentSet = equipment_json.into_serde().unwrap();
+    let monster: models::monster::Monster = from_js(monster_json)?;
+    let equipment_set: models::equipment::EquipmentSet = from_js(equipment_json)?;
+    let (character, skill) = parse_character_and_skill(character_json, skill_json)?;
+    // バフ・デバフが指定されていない場合はなし
+    let buffs: Vec<models::buff::Buff> = if buffs_json.is_undefined() {
+        Vec::new()
+    } else {
+        from_js(buffs_json)?
+    };
 
-    // ダメージ計算
-    let mut calculator = models::damage::DamageCalculator::new();
-    let result = calculator.calculate_damage(&equipment_set, &monster);
+    let version = formula_version
+        .as_deref()
+        .unwrap_or(models::formula::CURRENT_FORMULA);
+    let formula = models::formula::formula_by_version(version)
+        .ok_or_else(|| JsValue::from_str(&format!("不明な計算式のバージョンです: {}", version)))?;
+
+    // ダメージ計算（UI表示用に計算過程も含める）
+    let mut calculator = new_calculator(None, level_correction_json)?
+        .with_breakdown()
+        .with_buffs(buffs)
+        .with_formula(formula);
+    let result = calculator.calculate_damage(&character, &equipment_set, &skill, &monster);
 
     // 結果をJavaScriptに返す
-    to_value(&result).unwrap()
+    to_js(&result)
+}
+
+// 複数回攻撃のシミュレーションをJavaScriptに公開（ヒット数は `MAX_SIMULATED_HITS` まで）
+#[wasm_bindgen]
+pub fn simulate_damage(
+    monster_json: &JsValue,
+    equipment_json: &JsValue,
+    character_json: &JsValue,
+    skill_json: &JsValue,
+    hits: u32,
+    seed: Option<u32>,
+    level_correction_json: &JsValue,
+) -> Result<JsValue, JsValue> {
+    let monster: models::monster::Monster = from_js(monster_json)?;
+    let equipment_set: models::equipment::EquipmentSet = from_js(equipment_json)?;
+    let (character, skill) = parse_character_and_skill(character_json, skill_json)?;
+
+    let mut calculator = new_calculator(seed, level_correction_json)?;
+    let result =
+        calculator.simulate_multiple_hits(&character, &equipment_set, &skill, &monster, hits);
+
+    to_js(&result)
+}
+
+// ヒット数ごとの累積撃破確率をJavaScriptに公開
+#[wasm_bindgen]
+pub fn calculate_kill_probability(
+    monster_json: &JsValue,
+    equipment_json: &JsValue,
+    character_json: &JsValue,
+    skill_json: &JsValue,
+    seed: Option<u32>,
+    level_correction_json: &JsValue,
+) -> Result<JsValue, JsValue> {
+    let monster: models::monster::Monster = from_js(monster_json)?;
+    let equipment_set: models::equipment::EquipmentSet = from_js(equipment_json)?;
+    let (character, skill) = parse_character_and_skill(character_json, skill_json)?;
+
+    let mut calculator = new_calculator(seed, level_correction_json)?;
+    let damage = calculator.calculate_damage(&character, &equipment_set, &skill, &monster);
+    let result =
+        models::kill_probability::KillProbability::compute(&mut calculator, &damage, monster.hp);
+
+    to_js(&result)
+}
+
+// 目標のダメージ・ヒット数・撃破時間に必要な攻撃力などの逆算をJavaScriptに公開
+// （上限まで上げても届かない場合はnull）
+#[wasm_bindgen]
+pub fn solve_requirement(
+    monster_json: &JsValue,
+    equipment_json: &JsValue,
+    character_json: &JsValue,
+    skill_json: &JsValue,
+    target_json: &JsValue,
+    solve_for_json: &JsValue,
+    level_correction_json: &JsValue,
+) -> Result<JsValue, JsValue> {
+    let monster: models::monster::Monster = from_js(monster_json)?;
+    let equipment_set: models::equipment::EquipmentSet = from_js(equipment_json)?;
+    let (character, skill) = parse_character_and_skill(character_json, skill_json)?;
+    let target: models::solver::SolveTarget = from_js(target_json)?;
+    let solve_for: models::solver::SolveFor = from_js(solve_for_json)?;
+
+    let solver = models::solver::RequirementSolver::new(character, equipment_set, skill);
+    let mut calculator = new_calculator(None, level_correction_json)?;
+    let solution = solver.solve(&mut calculator, &monster, target, solve_for);
+
+    to_js(&solution)
+}
+
+// ステータスごとの感度（+1あたりのダメージ・撃破時間の変化）をJavaScriptに公開
+// （増分が指定されていない場合は全ステータスを1ずつ上げる）
+#[wasm_bindgen]
+pub fn analyze_sensitivity(
+    monster_json: &JsValue,
+    equipment_json: &JsValue,
+    character_json: &JsValue,
+    skill_json: &JsValue,
+    increments_json: &JsValue,
+    level_correction_json: &JsValue,
+) -> Result<JsValue, JsValue> {
+    let monster: models::monster::Monster = from_js(monster_json)?;
+    let equipment_set: models::equipment::EquipmentSet = from_js(equipment_json)?;
+    let (character, skill) = parse_character_and_skill(character_json, skill_json)?;
+
+    let mut calculator = new_calculator(None, level_correction_json)?;
+    let report = if increments_json.is_undefined() {
+        models::sensitivity::SensitivityReport::analyze(
+            &mut calculator,
+            &character,
+            &equipment_set,
+            &skill,
+            &monster,
+        )
+    } else {
+        let increments: Vec<(models::sensitivity::SensitivityStat, f32)> =
+            from_js(increments_json)?;
+        models::sensitivity::SensitivityReport::analyze_increments(
+            &mut calculator,
+            &character,
+            &equipment_set,
+            &skill,
+            &monster,
+            &increments,
+        )
+    };
+
+    to_js(&report)
+}
+
+// 所持装備からの装備セットの最適化をJavaScriptに公開
+// （目的が指定されていない場合は平均ダメージの最大化、件数が指定されていない場合は上位5件）
+#[wasm_bindgen]
+pub fn optimize_equipment(
+    inventory_json: &JsValue,
+    targets_json: &JsValue,
+    character_json: &JsValue,
+    skill_json: &JsValue,
+    goal_json: &JsValue,
+    top_n: Option<usize>,
+    level_correction_json: &JsValue,
+) -> Result<JsValue, JsValue> {
+    let inventory: Vec<models::equipment::Equipment> = from_js(inventory_json)?;
+    let targets: Vec<models::optimizer::WeightedMonster> = from_js(targets_json)?;
+    let (character, skill) = parse_character_and_skill(character_json, skill_json)?;
+    let goal: models::optimizer::OptimizeGoal = if goal_json.is_undefined() {
+        Default::default()
+    } else {
+        from_js(goal_json)?
+    };
+
+    let optimizer = models::optimizer::EquipmentOptimizer::new(character, skill)
+        .with_goal(goal)
+        .with_top_n(top_n.unwrap_or(models::optimizer::DEFAULT_TOP_N));
+    let mut calculator = new_calculator(None, level_correction_json)?;
+    let result = optimizer.optimize_weighted(&mut calculator, &inventory, &targets);
+
+    to_js(&result)
+}
+
+// スキル回しのシミュレーションをJavaScriptに公開
+#[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
+pub fn simulate_rotation(
+    monster_json: &JsValue,
+    equipment_json: &JsValue,
+    character_json: &JsValue,
+    skills_json: &JsValue,
+    priority_json: &JsValue,
+    time_limit: Option<f32>,
+    seed: Option<u32>,
+    level_correction_json: &JsValue,
+) -> Result<JsValue, JsValue> {
+    let monster: models::monster::Monster = from_js(monster_json)?;
+    let equipment_set: models::equipment::EquipmentSet = from_js(equipment_json)?;
+    let (character, _) = parse_character_and_skill(character_json, &JsValue::UNDEFINED)?;
+    let skills: Vec<models::skill::Skill> = from_js(skills_json)?;
+    let priority: Vec<String> = from_js(priority_json)?;
+
+    let mut simulator =
+        models::rotation::RotationSimulator::new(character, equipment_set, &skills, &priority)
+            .map_err(|error| JsValue::from_str(&error.to_string()))?;
+    if let Some(time_limit) = time_limit {
+        simulator = simulator.with_time_limit(time_limit);
+    }
+    let mut calculator = new_calculator(seed, level_correction_json)?;
+    let result = simulator.run(&mut calculator, &monster);
+
+    to_js(&result)
+}
+
+// ダメージ計算機を作成（シードが指定されていれば同じ乱数列を再現する）
+// レベル差補正が指定されていない場合は補正しない
+fn new_calculator(
+    seed: Option<u32>,
+    level_correction_json: &JsValue,
+) -> Result<models::damage::DamageCalculator, JsValue> {
+    let level_correction = if level_correction_json.is_undefined() {
+        models::level_correction::LevelCorrection::default()
+    } else {
+        from_js(level_correction_json)?
+    };
+    let calculator = match seed {
+        Some(seed) => models::damage::DamageCalculator::with_seed(seed),
+        None => models::damage::DamageCalculator::new(),
+    };
+    Ok(calculator.with_level_correction(level_correction))
+}
+
+// JavaScriptの値を変換（形式が違う・必須の項目がない場合はパニックせずエラーを返す）
+fn from_js<T: serde::de::DeserializeOwned>(value: &JsValue) -> Result<T, JsValue> {
+    from_value(value.clone()).map_err(JsValue::from)
+}
+
+// 結果をJavaScriptの値に変換（変換できない場合はパニックせずエラーを返す）
+// 2^53 以上になりうる u64 はモデル側で `utils::serialize_js_number` により丸めている
+fn to_js<T: serde::Serialize>(value: &T) -> Result<JsValue, JsValue> {
+    to_value(value).map_err(JsValue::from)
+}
+
+// キャラクターとスキルを変換（未指定の場合はデフォルトキャラクターと通常攻撃）
+fn parse_character_and_skill(
+    character_json: &JsValue,
+    skill_json: &JsValue,
+) -> Result<(models::character::Character, models::skill::Skill), JsValue> {
+    let character = if character_json.is_undefined() {
+        models::character::Character::new()
+    } else {
+        from_js(character_json)?
+    };
+    let skill = if skill_json.is_undefined() {
+        models::skill::Skill::normal_attack()
+    } else {
+        from_js(skill_json)?
+    };
+    Ok((character, skill))
 }
 
 // 画像処理関数をJavaScriptに公開（簡略化版）
@@ -62,10 +315,11 @@ pub async fn process_equipment_image(_image_data_url: &str) -> Result<JsValue, J
         attack: 100,
         defense: 0,
         element_value: 20,
+        element: models::element::Element::None,
         equipment_type: models::equipment::EquipmentType::Weapon,
         options: vec![],
     });
 
     // 結果をJavaScriptに返す
-    Ok(to_value(&equipment_set).unwrap())
+    to_js(&equipment_set)
 }
@@ -0,0 +1,245 @@
+use super::character::Character;
+use super::damage_category::{DamageCategory, DamageModifiers};
+use super::monster::Monster;
+use super::penetration::Penetration;
+use serde::{Deserialize, Serialize};
+
+/// バフ・デバフが影響するステータス
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum BuffStat {
+    AttackFlat,             // 攻撃力+N
+    AttackPercent,          // 攻撃力+N%
+    CriticalRate,           // クリティカル率+N%
+    CriticalDamage,         // クリティカル倍率+N%
+    StatusDefense,          // モンスターのステータス防御-N%
+    MagicResist,            // モンスターの魔法防御-N%
+    FixedDefense,           // モンスターの固定防御-N%
+    FixedReduction,         // モンスターの固定減少-N%
+    CutRate,                // モンスターのカット率-N%（ポイント）
+    Damage(DamageCategory), // 区分ごとのダメージ増加+N%
+}
+
+/// 同じバフを重ねたときの扱い
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StackingRule {
+    Refresh,    // 重ならず、効果時間だけ更新
+    Stack(u32), // 指定回数まで重なり、効果時間は更新
+}
+
+/// バフ・デバフ（自己バフ・パーティバフ・消耗品・モンスターへのデバフ）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Buff {
+    pub id: String,
+    pub name: String,
+    pub stat: BuffStat,
+    pub value: f32,    // 1スタックあたりの効果量
+    pub duration: f32, // 効果時間（秒）
+    pub stacking: StackingRule,
+    #[serde(default = "default_stacks")]
+    pub stacks: u32,
+}
+
+fn default_stacks() -> u32 {
+    1
+}
+
+impl Buff {
+    /// 新しいバフを作成
+    pub fn new(
+        id: impl Into<String>,
+        name: impl Into<String>,
+        stat: BuffStat,
+        value: f32,
+        duration: f32,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+            stat,
+            value,
+            duration,
+            stacking: StackingRule::Refresh,
+            stacks: 1,
+        }
+    }
+
+    /// 重ね掛けの扱いを設定
+    pub fn with_stacking(mut self, stacking: StackingRule) -> Self {
+        self.stacking = stacking;
+        self
+    }
+
+    /// スタック数を考慮した効果量
+    pub fn total_value(&self) -> f32 {
+        self.value * self.stacks as f32
+    }
+}
+
+/// 時間経過とともに管理されるバフの一覧
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BuffSet {
+    entries: Vec<(Buff, f32)>, // (バフ, 効果が切れる時刻)
+}
+
+impl BuffSet {
+    /// 新しい空のバフ一覧を作成
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+
+    /// 指定時刻にバフを付与（同じIDのバフは重ね掛けの扱いに従う）
+    pub fn apply(&mut self, buff: Buff, time: f32) {
+        let expires_at = time + buff.duration;
+
+        if let Some((active, active_expires_at)) = self
+            .entries
+            .iter_mut()
+            .find(|(active, expires)| active.id == buff.id && *expires > time)
+        {
+            if let StackingRule::Stack(max_stacks) = active.stacking {
+                active.stacks = (active.stacks + buff.stacks).min(max_stacks.max(1));
+            }
+            *active_expires_at = expires_at;
+            return;
+        }
+
+        self.entries.retain(|(active, _)| active.id != buff.id);
+        self.entries.push((buff, expires_at));
+    }
+
+    /// 指定時刻に有効なバフ
+    pub fn active_at(&self, time: f32) -> Vec<Buff> {
+        self.entries
+            .iter()
+            .filter(|(_, expires_at)| *expires_at > time)
+            .map(|(buff, _)| buff.clone())
+            .collect()
+    }
+}
+
+/// 有効なバフの効果量をステータスごとに合計したもの
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BuffTotals {
+    pub attack_flat: f32,
+    pub attack_percent: f32,
+    pub critical_rate: f32,
+    pub critical_damage: f32,
+    pub status_defense_down: f32,
+    pub magic_resist_down: f32,
+    pub fixed_defense_down: f32,
+    pub fixed_reduction_down: f32,
+    pub cut_rate_down: f32,
+    pub damage_modifiers: DamageModifiers,
+}
+
+impl BuffTotals {
+    /// バフの一覧を合計
+    pub fn from_buffs(buffs: &[Buff]) -> Self {
+        let mut totals = Self::default();
+        for buff in buffs {
+            let value = buff.total_value();
+            match buff.stat {
+                BuffStat::AttackFlat => totals.attack_flat += value,
+                BuffStat::AttackPercent => totals.attack_percent += value,
+                BuffStat::CriticalRate => totals.critical_rate += value,
+                BuffStat::CriticalDamage => totals.critical_damage += value,
+                BuffStat::StatusDefense => totals.status_defense_down += value,
+                BuffStat::MagicResist => totals.magic_resist_down += value,
+                BuffStat::FixedDefense => totals.fixed_defense_down += value,
+                BuffStat::FixedReduction => totals.fixed_reduction_down += value,
+                BuffStat::CutRate => totals.cut_rate_down += value,
+                BuffStat::Damage(category) => totals.damage_modifiers.add(category, value),
+            }
+        }
+        totals
+    }
+
+    /// 攻撃力にバフを適用
+    pub fn apply_to_attack(&self, attack: u64) -> u64 {
+        let flat = (attack as f64 + self.attack_flat as f64).max(0.0);
+        (flat * (1.0 + self.attack_percent as f64 / 100.0)).max(0.0) as u64
+    }
+
+    /// キャラクターにバフを適用
+    pub fn apply_to_character(&self, character: &Character) -> Character {
+        let mut character = character.clone();
+        character.critical_rate += self.critical_rate / 100.0;
+        character.critical_damage += self.critical_damage / 100.0;
+        character
+    }
+
+    /// モンスターにデバフを適用
+    ///
+    /// 防御値の減少は防御無視と同じく f64 で計算して端数を切り捨てる。
+    pub fn apply_to_monster(&self, monster: &Monster) -> Monster {
+        let reduce = Penetration::apply;
+
+        let mut monster = monster.clone();
+        // 魔法防御はステータス防御のデバフの影響を受けない
+        monster.magic_resist = Some(reduce(
+            monster.effective_magic_resist(),
+            self.magic_resist_down,
+        ));
+        monster.defense = reduce(monster.defense, self.status_defense_down);
+        monster.fixed_defense = reduce(monster.fixed_defense, self.fixed_defense_down);
+        monster.fixed_reduction = reduce(monster.fixed_reduction, self.fixed_reduction_down);
+        monster.cut_rate = (monster.cut_rate - self.cut_rate_down / 100.0).clamp(0.0, 1.0);
+        monster
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_refresh_and_stack_rules() {
+        let mut buffs = BuffSet::new();
+        let refresh = Buff::new("might", "マイト", BuffStat::AttackPercent, 10.0, 10.0);
+        let stacking = Buff::new("rage", "レイジ", BuffStat::CriticalRate, 5.0, 10.0)
+            .with_stacking(StackingRule::Stack(3));
+
+        buffs.apply(refresh.clone(), 0.0);
+        buffs.apply(refresh, 5.0);
+        for time in [0.0, 1.0, 2.0, 3.0] {
+            buffs.apply(stacking.clone(), time);
+        }
+
+        let active = buffs.active_at(12.0);
+        let totals = BuffTotals::from_buffs(&active);
+        // 効果時間が更新されるが重ならない
+        assert_eq!(totals.attack_percent, 10.0);
+        // 3スタックで頭打ち
+        assert_eq!(totals.critical_rate, 15.0);
+
+        // 効果時間切れ
+        assert!(buffs.active_at(15.0).is_empty());
+    }
+
+    #[test]
+    fn test_monster_debuffs() {
+        let monster = Monster::new("boss", "ボス", 40, 1500, 1500, 40000, 9000, 0.5, 120);
+        let debuffs = vec![
+            Buff::new(
+                "armor_break",
+                "防御ダウン",
+                BuffStat::FixedDefense,
+                20.0,
+                10.0,
+            ),
+            Buff::new("cut_down", "カット率ダウン", BuffStat::CutRate, 10.0, 10.0),
+        ];
+
+        let debuffed = BuffTotals::from_buffs(&debuffs).apply_to_monster(&monster);
+        assert_eq!(debuffed.fixed_defense, 32000);
+        assert!((debuffed.cut_rate - 0.4).abs() < 1e-6);
+        assert_eq!(debuffed.defense, 1500);
+
+        // デバフのない防御値は f32 の精度を超えても変わらない
+        let wall = Monster::new("wall", "壁", 40, 1, 16_777_217, 0, 0, 0.0, 0);
+        let debuffed = BuffTotals::from_buffs(&debuffs).apply_to_monster(&wall);
+        assert_eq!(debuffed.defense, 16_777_217);
+    }
+}
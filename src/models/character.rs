@@ -0,0 +1,177 @@
+use super::equipment::{EquipmentSet, StatTotals};
+use serde::{Deserialize, Serialize};
+
+/// 基本クリティカル倍率
+pub const BASE_CRITICAL_MULTIPLIER: f32 = 1.5;
+/// 主ステータス1ポイントあたりの物理攻撃力
+pub const PHYSICAL_ATTACK_PER_STAT: u32 = 2;
+/// INT1ポイントあたりの魔法攻撃力
+pub const MAGIC_ATTACK_PER_INT: u32 = 2;
+/// 基本攻撃速度（1秒あたりの通常攻撃回数）
+pub const BASE_ATTACK_SPEED: f32 = 1.0;
+
+/// キャラクターの種類
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CharacterClass {
+    #[default]
+    Lucian, // ルシアン
+    Boris,    // ボリス
+    Maximin,  // マキシミン
+    Sibelin,  // シベリン
+    Tichel,   // ティチエル
+    Joshua,   // ジョシュア
+    Mila,     // ミラ
+    Isolet,   // イソレット
+    Nayatrey, // ナヤトレイ
+    Chloe,    // クロエ
+}
+
+/// 攻撃力の元になる主ステータス
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MainStat {
+    Stab, // 突き
+    Hack, // 斬り
+    Int,  // 知能
+}
+
+impl CharacterClass {
+    /// キャラクターの主ステータス
+    pub fn main_stat(self) -> MainStat {
+        match self {
+            CharacterClass::Lucian
+            | CharacterClass::Boris
+            | CharacterClass::Maximin
+            | CharacterClass::Nayatrey => MainStat::Hack,
+            CharacterClass::Sibelin
+            | CharacterClass::Mila
+            | CharacterClass::Isolet
+            | CharacterClass::Chloe => MainStat::Stab,
+            CharacterClass::Tichel | CharacterClass::Joshua => MainStat::Int,
+        }
+    }
+}
+
+/// キャラクターのステータス
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Stats {
+    pub stab: u32, // STAB
+    pub hack: u32, // HACK
+    pub int: u32,  // INT
+    pub def: u32,  // DEF
+    pub mr: u32,   // MR
+    pub dex: u32,  // DEX
+    pub agi: u32,  // AGI
+}
+
+/// キャラクター
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Character {
+    pub level: u32,
+    pub class: CharacterClass,
+    pub stats: Stats,
+    pub base_attack: u32,     // 基本攻撃力
+    pub critical_rate: f32,   // クリティカル率 (0.0 ~ 1.0)
+    pub critical_damage: f32, // クリティカル倍率
+    #[serde(default = "default_attack_speed")]
+    pub attack_speed: f32, // 1秒あたりの通常攻撃回数
+    #[serde(default)]
+    pub accuracy: u32, // 命中
+}
+
+fn default_attack_speed() -> f32 {
+    BASE_ATTACK_SPEED
+}
+
+impl Character {
+    /// 新しいキャラクターを作成
+    pub fn new() -> Self {
+        Self {
+            level: 1,
+            class: CharacterClass::default(),
+            stats: Stats::default(),
+            base_attack: 0,
+            critical_rate: 0.0,
+            critical_damage: BASE_CRITICAL_MULTIPLIER,
+            attack_speed: BASE_ATTACK_SPEED,
+            accuracy: 0,
+        }
+    }
+
+    /// レベルとキャラクターの種類を設定
+    pub fn with_class(mut self, level: u32, class: CharacterClass) -> Self {
+        self.level = level;
+        self.class = class;
+        self
+    }
+
+    /// ステータスを設定
+    pub fn with_stats(mut self, stats: Stats) -> Self {
+        self.stats = stats;
+        self
+    }
+
+    /// 基本攻撃力を設定
+    pub fn with_base_attack(mut self, base_attack: u32) -> Self {
+        self.base_attack = base_attack;
+        self
+    }
+
+    /// クリティカル率を設定
+    pub fn with_critical(mut self, critical_rate: f32, critical_damage: f32) -> Self {
+        self.critical_rate = critical_rate;
+        self.critical_damage = critical_damage;
+        self
+    }
+
+    /// 攻撃速度を設定
+    pub fn with_attack_speed(mut self, attack_speed: f32) -> Self {
+        self.attack_speed = attack_speed;
+        self
+    }
+
+    /// 命中を設定
+    pub fn with_accuracy(mut self, accuracy: u32) -> Self {
+        self.accuracy = accuracy;
+        self
+    }
+
+    /// 装備込みの物理攻撃力を計算
+    ///
+    /// 魔法職はSTABとHACKの高い方を物理攻撃に使う。
+    pub fn physical_attack(&self, equipment: &EquipmentSet) -> u64 {
+        let stat = match self.class.main_stat() {
+            MainStat::Stab => self.stats.stab,
+            MainStat::Hack => self.stats.hack,
+            MainStat::Int => self.stats.stab.max(self.stats.hack),
+        };
+
+        Self::apply_equipment(
+            self.base_attack as u64 + stat as u64 * PHYSICAL_ATTACK_PER_STAT as u64,
+            &equipment.stat_totals(),
+        )
+    }
+
+    /// 装備込みの魔法攻撃力を計算
+    pub fn magic_attack(&self, equipment: &EquipmentSet) -> u64 {
+        Self::apply_equipment(
+            self.base_attack as u64 + self.stats.int as u64 * MAGIC_ATTACK_PER_INT as u64,
+            &equipment.stat_totals(),
+        )
+    }
+
+    /// 装備の攻撃力と 攻撃力+N / 攻撃力+N% のオプションを適用
+    ///
+    /// 端数は切り捨て、u64 に収まらない値は u64::MAX に飽和させる。
+    fn apply_equipment(attack: u64, totals: &StatTotals) -> u64 {
+        let options = &totals.options;
+        let flat =
+            (attack.saturating_add(totals.attack) as f64 + options.attack_flat as f64).max(0.0);
+        (flat * (1.0 + options.attack_percent as f64 / 100.0)).max(0.0) as u64
+    }
+}
+
+impl Default for Character {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,129 +1,592 @@
+use super::buff::{Buff, BuffTotals};
+use super::character::Character;
+use super::damage_category::{DamageCategory, DamageModifiers};
+use super::element::Element;
 use super::equipment::EquipmentSet;
+use super::formula::{CurrentFormula, DamageFormula, FormulaInput};
+use super::level_correction::LevelCorrection;
 use super::monster::Monster;
+use super::penetration::Penetration;
+use super::simulation::{SimulationResult, MAX_SIMULATED_HITS};
+use super::skill::{DamageType, Skill};
+use crate::utils::format_time;
 use rand::prelude::*;
+use rand_xoshiro::Xoshiro256PlusPlus;
 use serde::{Deserialize, Serialize};
 
 /// ダメージ計算結果
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DamageResult {
-    pub base_damage: u32,
-    pub min_damage: u32,
-    pub max_damage: u32,
-    pub average_damage: f32,
+    #[serde(serialize_with = "crate::utils::serialize_js_number")]
+    pub base_damage: u64,
+    #[serde(serialize_with = "crate::utils::serialize_js_number")]
+    pub min_damage: u64,
+    #[serde(serialize_with = "crate::utils::serialize_js_number")]
+    pub max_damage: u64,
+    pub average_damage: f64,
     pub critical_rate: f32,
-    pub critical_damage: u32,
+    #[serde(serialize_with = "crate::utils::serialize_js_number")]
+    pub critical_damage: u64,
+    #[serde(default = "default_hit_chance")]
+    pub hit_chance: f32, // 命中率 (0.0 ~ 1.0)
     pub element_bonus: f32,
-    pub hits_to_kill: u32,
+    #[serde(serialize_with = "crate::utils::serialize_js_number")]
+    pub hits_to_kill: u64,
+    pub damage_type: DamageType, // 使用した計算経路
+    #[serde(default, serialize_with = "crate::utils::serialize_js_number")]
+    pub attack: u64, // 計算に使った攻撃力（バフ適用後）
+    pub hit_count: u32,          // 1回の使用でのヒット数
+    pub damage_per_cast: f64,    // 1回の使用での平均ダメージ
+    #[serde(serialize_with = "crate::utils::serialize_js_number")]
+    pub casts_to_kill: u64, // 倒すのに必要な使用回数
+    pub cycle_time: f32,         // 1回の使用にかかる時間（秒）
+    pub dps: f64,                // 秒間ダメージ
+    pub time_to_kill: f32,       // 倒すまでの時間（秒）
+    pub time_to_kill_text: String,
+    #[serde(default)]
+    pub breakdown: Option<Vec<DamageStage>>, // 計算過程（有効時のみ）
+    #[serde(default)]
+    pub formula_version: String, // 使用した計算式のバージョン
+}
+
+fn default_hit_chance() -> f32 {
+    1.0
+}
+
+impl DamageResult {
+    /// 1回の使用にかかる時間から秒間ダメージと撃破時間を設定
+    ///
+    /// 撃破時間はスキル回しと同じく、最後の使用の動作が終わった時点とする
+    /// （最後の使用の後のクールタイムは含めない）。
+    fn with_timing(mut self, cycle_time: f32, action_time: f32) -> Self {
+        self.cycle_time = cycle_time;
+        self.dps = self.damage_per_cast / cycle_time as f64;
+        self.time_to_kill = match self.casts_to_kill {
+            0 => 0.0,
+            casts => ((casts - 1) as f64 * cycle_time as f64 + action_time as f64) as f32,
+        };
+        self.time_to_kill_text = format_time(self.time_to_kill);
+        self
+    }
+}
+
+/// ダメージ計算の段階
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DamageStageKind {
+    SkillMultiplier,                // スキル倍率
+    StatusDefense,                  // ステータス防御
+    MagicResist,                    // 魔法防御
+    FixedDefense,                   // 固定防御
+    FixedReduction,                 // 固定減少
+    CutRate,                        // カット率
+    ElementBonus,                   // 属性ボーナス
+    DamageCategory(DamageCategory), // ダメージ増加の区分ごとの倍率
+    LevelCorrection,                // レベル差補正
+    FixedDamage,                    // 固定ダメージ
+}
+
+impl DamageStageKind {
+    /// 真ダメージが飛ばす段階
+    pub const TRUE_DAMAGE_BYPASS: [DamageStageKind; 4] = [
+        DamageStageKind::StatusDefense,
+        DamageStageKind::MagicResist,
+        DamageStageKind::FixedDefense,
+        DamageStageKind::FixedReduction,
+    ];
+
+    /// 「固定防御」などの名前から段階を取得（オプション文字列の解析用）
+    pub fn from_label(label: &str) -> Option<Self> {
+        match label {
+            "ステータス防御" => Some(DamageStageKind::StatusDefense),
+            "魔法防御" => Some(DamageStageKind::MagicResist),
+            "固定防御" => Some(DamageStageKind::FixedDefense),
+            "固定減少" => Some(DamageStageKind::FixedReduction),
+            "カット率" => Some(DamageStageKind::CutRate),
+            "属性ボーナス" => Some(DamageStageKind::ElementBonus),
+            "レベル差補正" => Some(DamageStageKind::LevelCorrection),
+            _ => None,
+        }
+    }
+}
+
+/// 段階での補正の掛け方
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StageOperation {
+    Subtract, // 減算
+    Multiply, // 乗算
+}
+
+/// ダメージ計算の1段階分の記録
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DamageStage {
+    pub kind: DamageStageKind,
+    pub operation: StageOperation,
+    pub input: f64,
+    pub modifier: f64,
+    pub output: f64,
+    pub floored: bool, // 最低ダメージ1が適用されたか
+    #[serde(default)]
+    pub penetration: f32, // この段階で無視した割合%（防御・カット率のみ）
+    #[serde(default)]
+    pub bypassed: bool, // スキル・オプションの指定で計算を飛ばしたか
+}
+
+/// 計算過程を記録しながらダメージを段階的に求める
+///
+/// 値は f64 で持ち、端数の扱いは段階ごとに次のとおり。
+/// - スキル倍率: 乗算直後に切り捨て
+/// - 防御の減算: 貫通後の防御値を切り捨ててから整数同士で減算
+/// - それ以降の乗算: 端数を残したまま掛け、最後にまとめて切り捨て
+/// - u64 に収まらない値は u64::MAX に飽和させる
+///
+/// 各段階の順番は `DamageFormula` の実装が決める。
+pub struct DamagePipeline {
+    value: f64,
+    stages: Option<Vec<DamageStage>>,
+    bypass: Vec<DamageStageKind>, // 計算を飛ばす段階
+}
+
+impl DamagePipeline {
+    fn new(value: f64, record: bool) -> Self {
+        Self {
+            value,
+            stages: record.then(Vec::new),
+            bypass: Vec::new(),
+        }
+    }
+
+    /// 計算を飛ばす段階を追加
+    fn with_bypass<'a>(mut self, stages: impl IntoIterator<Item = &'a DamageStageKind>) -> Self {
+        self.bypass.extend(stages);
+        self
+    }
+
+    /// 段階を飛ばす場合は入力をそのまま出力として記録
+    fn skip(&mut self, kind: DamageStageKind, operation: StageOperation) -> bool {
+        if !self.bypass.contains(&kind) {
+            return false;
+        }
+        let identity = match operation {
+            StageOperation::Subtract => 0.0,
+            StageOperation::Multiply => 1.0,
+        };
+        self.push(kind, operation, identity, self.value, false);
+        if let Some(last) = self.stages.as_mut().and_then(|stages| stages.last_mut()) {
+            last.bypassed = true;
+        }
+        true
+    }
+
+    fn push(
+        &mut self,
+        kind: DamageStageKind,
+        operation: StageOperation,
+        modifier: f64,
+        output: f64,
+        floored: bool,
+    ) {
+        if let Some(stages) = &mut self.stages {
+            stages.push(DamageStage {
+                kind,
+                operation,
+                input: self.value,
+                modifier,
+                output,
+                floored,
+                penetration: 0.0,
+                bypassed: false,
+            });
+        }
+        self.value = output;
+    }
+
+    /// 貫通を適用した防御値を減算（防御値以下なら最低ダメージ1）
+    pub fn subtract(&mut self, kind: DamageStageKind, amount: u32, penetration: f32) {
+        if self.skip(kind, StageOperation::Subtract) {
+            return;
+        }
+        let amount = Penetration::apply(amount, penetration) as f64;
+        let floored = self.value <= amount;
+        let output = if floored { 1.0 } else { self.value - amount };
+        self.push(kind, StageOperation::Subtract, amount, output, floored);
+        self.set_penetration(penetration);
+    }
+
+    /// 直前の段階に貫通の割合を記録（飛ばした段階には記録しない）
+    pub fn set_penetration(&mut self, penetration: f32) {
+        if let Some(last) = self.stages.as_mut().and_then(|stages| stages.last_mut()) {
+            if !last.bypassed {
+                last.penetration = penetration;
+            }
+        }
+    }
+
+    /// 倍率を乗算（端数は最後にまとめて切り捨てる）
+    pub fn multiply(&mut self, kind: DamageStageKind, multiplier: f32) {
+        if self.skip(kind, StageOperation::Multiply) {
+            return;
+        }
+        let multiplier = multiplier as f64;
+        let output = self.value * multiplier;
+        self.push(kind, StageOperation::Multiply, multiplier, output, false);
+    }
+
+    /// 現在の値を切り捨てる
+    pub fn truncate(&mut self) {
+        self.value = self.value.trunc();
+        if let Some(last) = self.stages.as_mut().and_then(|stages| stages.last_mut()) {
+            last.output = self.value;
+        }
+    }
+
+    /// 切り捨てて最低ダメージ1を適用（u64 の範囲に飽和）
+    fn finish(mut self) -> (u64, Option<Vec<DamageStage>>) {
+        let damage = self.value as u64;
+        if damage < 1 {
+            if let Some(last) = self.stages.as_mut().and_then(|stages| stages.last_mut()) {
+                last.output = 1.0;
+                last.floored = true;
+            }
+        }
+        (damage.max(1), self.stages)
+    }
 }
 
 /// ダメージ計算機
 #[derive(Debug)]
 pub struct DamageCalculator {
-    rng: SmallRng,
+    rng: Xoshiro256PlusPlus,
+    seed: u32,
+    record_breakdown: bool,
+    buffs: Vec<Buff>, // 有効なバフ・デバフ
+    level_correction: LevelCorrection,
+    formula: Box<dyn DamageFormula>,
 }
 
 impl DamageCalculator {
-    /// 新しいダメージ計算機を作成
+    /// 新しいダメージ計算機を作成（シードはランダムに決定）
     pub fn new() -> Self {
+        Self::with_seed(rand::random())
+    }
+
+    /// シードを指定してダメージ計算機を作成
+    ///
+    /// 同じシードからは同じ乱数列が得られるため、シミュレーション結果を再現できる。
+    /// 乱数生成器は Xoshiro256++ に固定しているので、ネイティブとブラウザでも同じ結果になる。
+    pub fn with_seed(seed: u32) -> Self {
         Self {
-            rng: SmallRng::from_entropy(),
+            rng: Xoshiro256PlusPlus::seed_from_u64(seed as u64),
+            seed,
+            record_breakdown: false,
+            buffs: Vec::new(),
+            level_correction: LevelCorrection::default(),
+            formula: Box::new(CurrentFormula),
         }
     }
 
-    /// 装備セットとモンスターに基づいてダメージを計算
+    /// 乱数シードを取得
+    pub fn seed(&self) -> u32 {
+        self.seed
+    }
+
+    /// 計算過程を結果に含める
+    pub fn with_breakdown(mut self) -> Self {
+        self.record_breakdown = true;
+        self
+    }
+
+    /// 有効なバフ・デバフを設定
+    pub fn with_buffs(mut self, buffs: Vec<Buff>) -> Self {
+        self.buffs = buffs;
+        self
+    }
+
+    /// レベル差補正を設定
+    pub fn with_level_correction(mut self, level_correction: LevelCorrection) -> Self {
+        self.level_correction = level_correction;
+        self
+    }
+
+    /// 計算式を設定
+    pub fn with_formula(mut self, formula: Box<dyn DamageFormula>) -> Self {
+        self.formula = formula;
+        self
+    }
+
+    /// 使用する計算式のバージョン
+    pub fn formula_version(&self) -> &'static str {
+        self.formula.version()
+    }
+
+    /// 有効なバフ・デバフ
+    pub fn buffs(&self) -> &[Buff] {
+        &self.buffs
+    }
+
+    /// 有効なバフ・デバフを差し替え
+    pub fn set_buffs(&mut self, buffs: Vec<Buff>) {
+        self.buffs = buffs;
+    }
+
+    /// キャラクター・装備セット・スキルとモンスターに基づいてダメージを計算
     pub fn calculate_damage(
         &mut self,
+        character: &Character,
         equipment: &EquipmentSet,
+        skill: &Skill,
         monster: &Monster,
     ) -> DamageResult {
-        // 基本攻撃力と防御力
-        let attack = equipment.total_attack();
-        let status_defense = monster.defense;
-        let fixed_defense = monster.fixed_defense;
-        let fixed_reduction = monster.fixed_reduction;
-        let cut_rate = monster.cut_rate;
-
-        // ステータス防御によるダメージ減少
-        let after_status_defense = if attack > status_defense {
-            let diff = attack - status_defense;
-            diff
-        } else {
-            1 // 最低ダメージは1
-        };
+        // バフとデバフを両者に適用してから計算する
+        let buffs = BuffTotals::from_buffs(&self.buffs);
+        let character = &buffs.apply_to_character(character);
+        let monster = &buffs.apply_to_monster(monster);
 
-        // 固定防御と固定減少によるダメージ計算
-        let after_fixed_defense = if after_status_defense > fixed_defense {
-            after_status_defense - fixed_defense
-        } else {
-            1 // 最低ダメージは1
+        // 固定ダメージは防御・属性・クリティカルの影響を受けない
+        if skill.damage_type == DamageType::Fixed {
+            let mut pipeline =
+                DamagePipeline::new(skill.fixed_damage as f64, self.record_breakdown);
+            pipeline.multiply(DamageStageKind::FixedDamage, 1.0);
+            let (damage, breakdown) = pipeline.finish();
+            let mut result = Self::build_result(
+                monster, skill, damage, damage, damage, 0.0, damage, 1.0, 0.0,
+            )
+            .with_timing(
+                skill.cycle_time(character.attack_speed),
+                skill.action_time(character.attack_speed),
+            );
+            result.breakdown = breakdown;
+            result.formula_version = self.formula_version().to_string();
+            return result;
+        }
+
+        // 物理・真ダメージは物理攻撃力とステータス防御、魔法は魔法攻撃力と魔法防御で計算する
+        let (attack, defense_stage, defense) = match skill.damage_type {
+            DamageType::Magic => (
+                character.magic_attack(equipment),
+                DamageStageKind::MagicResist,
+                monster.effective_magic_resist(),
+            ),
+            _ => (
+                character.physical_attack(equipment),
+                DamageStageKind::StatusDefense,
+                monster.defense,
+            ),
         };
 
-        // 固定減少の適用
-        let after_fixed_reduction = if after_fixed_defense > fixed_reduction {
-            after_fixed_defense - fixed_reduction
-        } else {
-            1 // 最低ダメージは1
+        let totals = equipment.stat_totals();
+        let options = &totals.options;
+
+        // 真ダメージ・スキル・装備オプションで指定された段階は計算を飛ばす
+        let true_damage_bypass: &[DamageStageKind] = match skill.damage_type {
+            DamageType::True => &DamageStageKind::TRUE_DAMAGE_BYPASS,
+            _ => &[],
         };
 
-        // カット率の適用
-        let base_damage = (after_fixed_reduction as f32 * (1.0 - cut_rate)) as u32;
+        // 装備オプションとスキルの防御貫通
+        let penetration = options.penetration().combine(&skill.penetration);
 
-        // クリティカルダメージ（基本ダメージの1.5倍）
-        let critical_damage = (base_damage as f32 * 1.5) as u32;
+        // 属性ボーナス（属性値 × 相性倍率 - 属性耐性値）
+        let element = skill.element.unwrap_or(equipment.element());
+        let element_bonus = element.bonus(
+            totals.element_value(element),
+            monster.element,
+            monster.element_resistance,
+        );
+
+        // 装備オプション・スキル・バフのダメージ増加を区分ごとにまとめる
+        let mut modifiers = DamageModifiers::from_options(options, skill, element);
+        modifiers.extend(&skill.damage_modifiers);
+        modifiers.extend_from(&buffs.damage_modifiers);
+        if element == Element::None {
+            // 無属性攻撃には属性攻撃%が乗らない
+            modifiers.remove(DamageCategory::ElementAttack);
+        }
+
+        // バフ適用後の攻撃力から、計算式に従って基本ダメージを求める
+        let attack = buffs.apply_to_attack(attack);
+        let input = FormulaInput {
+            skill_multiplier: skill.multiplier(),
+            defense_stage,
+            defense,
+            fixed_defense: monster.fixed_defense,
+            fixed_reduction: monster.fixed_reduction,
+            cut_rate: monster.cut_rate,
+            penetration,
+            element_bonus,
+            modifiers,
+            level_multiplier: self
+                .level_correction
+                .multiplier(character.level, monster.level),
+        };
+        let mut pipeline = DamagePipeline::new(attack as f64, self.record_breakdown)
+            .with_bypass(true_damage_bypass)
+            .with_bypass(&skill.bypass_stages)
+            .with_bypass(&options.bypass_stages);
+        self.formula.apply(&input, &mut pipeline);
+        let (base_damage, breakdown) = pipeline.finish();
+
+        // クリティカル率と倍率（キャラクター + 装備オプション）
+        let critical_rate =
+            (character.critical_rate + options.critical_rate / 100.0).clamp(0.0, 1.0);
+        let critical_multiplier = character.critical_damage + options.critical_damage / 100.0;
+        let critical_damage = ((base_damage as f64 * critical_multiplier as f64) as u64).max(1);
 
         // 通常ダメージ範囲（基本ダメージの±10%）
-        let min_damage = ((base_damage as f32 * 0.9) as u32).max(1);
-        let max_damage = ((base_damage as f32 * 1.1) as u32).max(1);
+        let min_damage = ((base_damage as f64 * 0.9) as u64).max(1);
+        let max_damage = ((base_damage as f64 * 1.1) as u64).max(1);
 
-        // 平均ダメージ（クリティカル率を考慮）
-        let normal_avg = (min_damage + max_damage) as f32 / 2.0;
-        let average_damage = normal_avg;
+        // 命中と回避による命中率（固定ダメージ以外）
+        let hit_chance = monster.hit_chance(character.accuracy);
 
-        // 倒すのに必要なヒット数
-        let hits_to_kill = (monster.hp as f32 / average_damage).ceil() as u32;
+        let mut result = Self::build_result(
+            monster,
+            skill,
+            base_damage,
+            min_damage,
+            max_damage,
+            critical_rate,
+            critical_damage,
+            hit_chance,
+            element_bonus,
+        )
+        .with_timing(
+            skill.cycle_time(character.attack_speed),
+            skill.action_time(character.attack_speed),
+        );
+        result.attack = attack;
+        result.breakdown = breakdown;
+        result.formula_version = self.formula_version().to_string();
+        result
+    }
+
+    /// ダメージ範囲から平均ダメージと撃破までの回数を求めて結果をまとめる
+    #[allow(clippy::too_many_arguments)]
+    fn build_result(
+        monster: &Monster,
+        skill: &Skill,
+        base_damage: u64,
+        min_damage: u64,
+        max_damage: u64,
+        critical_rate: f32,
+        critical_damage: u64,
+        hit_chance: f32,
+        element_bonus: f32,
+    ) -> DamageResult {
+        // 平均ダメージ（クリティカル率と命中率を考慮）
+        let normal_avg = (min_damage as f64 + max_damage as f64) / 2.0;
+        let rate = critical_rate as f64;
+        let average_damage =
+            (normal_avg * (1.0 - rate) + critical_damage as f64 * rate) * hit_chance as f64;
+
+        // 倒すのに必要なヒット数と使用回数（切り上げ、u64 の範囲に飽和）
+        let hit_count = skill.hit_count.max(1);
+        let damage_per_cast = average_damage * hit_count as f64;
+        let hits_to_kill = (monster.hp as f64 / average_damage).ceil() as u64;
+        let casts_to_kill = (monster.hp as f64 / damage_per_cast).ceil() as u64;
 
         DamageResult {
             base_damage,
             min_damage,
             max_damage,
             average_damage,
-            critical_rate: 0.0, // TODO: クリティカル率を計算する
+            critical_rate,
             critical_damage,
-            element_bonus: 0.0, // TODO: 属性ボーナスを計算する
+            hit_chance,
+            element_bonus,
             hits_to_kill,
+            damage_type: skill.damage_type,
+            attack: 0,
+            hit_count,
+            damage_per_cast,
+            casts_to_kill,
+            cycle_time: 0.0,
+            dps: 0.0,
+            time_to_kill: 0.0,
+            time_to_kill_text: String::new(),
+            breakdown: None,
+            formula_version: String::new(),
         }
     }
 
     /// 1回の攻撃でのダメージをシミュレート
-    pub fn simulate_single_hit(&mut self, equipment: &EquipmentSet, monster: &Monster) -> u32 {
-        let damage_result = self.calculate_damage(equipment, monster);
+    pub fn simulate_single_hit(
+        &mut self,
+        character: &Character,
+        equipment: &EquipmentSet,
+        skill: &Skill,
+        monster: &Monster,
+    ) -> u64 {
+        let damage_result = self.calculate_damage(character, equipment, skill, monster);
+        self.roll_hit(&damage_result).0
+    }
+
+    /// 計算結果から1ヒット分のダメージを抽選（ダメージ, クリティカルかどうか）
+    ///
+    /// 外れた場合はダメージ0（命中すれば最低1ダメージのため区別できる）。
+    pub fn roll_hit(&mut self, damage_result: &DamageResult) -> (u64, bool) {
+        // 必中でなければ命中判定
+        if damage_result.hit_chance < 1.0 && self.rng.gen::<f32>() >= damage_result.hit_chance {
+            return (0, false);
+        }
 
         // クリティカルヒットかどうか判定
         let is_critical = self.rng.gen::<f32>() < damage_result.critical_rate;
 
         if is_critical {
-            damage_result.critical_damage
+            (damage_result.critical_damage, true)
         } else {
             // 通常ダメージの範囲内でランダムに決定
-            self.rng
-                .gen_range(damage_result.min_damage..=damage_result.max_damage)
+            let damage = self
+                .rng
+                .gen_range(damage_result.min_damage..=damage_result.max_damage);
+            (damage, false)
         }
     }
 
-    /// 複数回の攻撃をシミュレートして平均ダメージを計算
+    /// スキル1回分（全ヒット）のダメージをシミュレート
+    pub fn simulate_cast(
+        &mut self,
+        character: &Character,
+        equipment: &EquipmentSet,
+        skill: &Skill,
+        monster: &Monster,
+    ) -> u64 {
+        (0..skill.hit_count.max(1))
+            .map(|_| self.simulate_single_hit(character, equipment, skill, monster))
+            .fold(0, u64::saturating_add)
+    }
+
+    /// 複数回の攻撃をシミュレートしてダメージ分布を集計
+    ///
+    /// ヒット数は `MAX_SIMULATED_HITS` までに抑える（結果の `hits` が実際に抽選した数）。
     pub fn simulate_multiple_hits(
         &mut self,
+        character: &Character,
         equipment: &EquipmentSet,
+        skill: &Skill,
         monster: &Monster,
         hits: u32,
-    ) -> f32 {
-        let mut total_damage = 0;
+    ) -> SimulationResult {
+        let damage_result = self.calculate_damage(character, equipment, skill, monster);
+        let hits = hits.min(MAX_SIMULATED_HITS);
+        let mut samples = Vec::with_capacity(hits as usize);
+        let mut critical_count = 0;
 
         for _ in 0..hits {
-            total_damage += self.simulate_single_hit(equipment, monster);
+            let (damage, is_critical) = self.roll_hit(&damage_result);
+            samples.push(damage);
+            if is_critical {
+                critical_count += 1;
+            }
         }
 
-        total_damage as f32 / hits as f32
+        SimulationResult::from_samples(self.seed, &samples, critical_count, monster.hp)
+    }
+}
+
+impl Default for DamageCalculator {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -131,7 +594,13 @@ impl DamageCalculator {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::equipment::{Equipment, EquipmentType};
+    use crate::models::buff::BuffStat;
+    use crate::models::character::{CharacterClass, Stats};
+    use crate::models::damage_category::DamageModifier;
+    use crate::models::element::Element;
+    use crate::models::equipment::{Equipment, EquipmentOption, EquipmentType};
+    use crate::models::monster::create_default_monsters;
+    use crate::utils::MAX_SAFE_INTEGER;
 
     #[test]
     fn test_basic_damage_calculation() {
@@ -144,6 +613,7 @@ mod tests {
             attack: 100,
             defense: 0,
             element_value: 20,
+            element: Element::Fire,
             options: vec![],
         };
 
@@ -161,10 +631,16 @@ mod tests {
             0,    // 固定減少
             0.30, // カット率 (30%)
             10,   // 属性値
-        );
+        )
+        .with_element(Element::Water);
 
         // ダメージ計算
-        let damage = calculator.calculate_damage(&equipment_set, &monster);
+        let damage = calculator.calculate_damage(
+            &Character::new(),
+            &equipment_set,
+            &Skill::normal_attack(),
+            &monster,
+        );
 
         // 基本ダメージの検証（新しい計算式に基づく）
         // 攻撃力100 - ステータス防御50 = 50
@@ -176,4 +652,634 @@ mod tests {
         // 属性ボーナスの検証（火属性vs水属性なので不利、ボーナスなし）
         assert_eq!(damage.element_bonus, 0.0);
     }
+
+    fn critical_test_setup(critical_option: f32) -> (EquipmentSet, Monster) {
+        let equipment_set = EquipmentSet {
+            weapon: Some(
+                Equipment::test_weapon(1100, Element::None).with_test_option(
+                    &format!("クリティカル率+{}%", critical_option),
+                    critical_option,
+                ),
+            ),
+            ..EquipmentSet::new()
+        };
+
+        let monster = Monster::new(
+            "test_monster",
+            "テストモンスター",
+            10,
+            100000,
+            100,
+            0,
+            0,
+            0.0,
+            0,
+        );
+
+        (equipment_set, monster)
+    }
+
+    #[test]
+    fn test_critical_rate_from_character_and_options() {
+        let mut calculator = DamageCalculator::new();
+        let (equipment_set, monster) = critical_test_setup(3.0);
+        let character = Character::new().with_critical(0.10, 2.0);
+
+        let damage = calculator.calculate_damage(
+            &character,
+            &equipment_set,
+            &Skill::normal_attack(),
+            &monster,
+        );
+
+        // 1100 - 100 = 1000
+        assert_eq!(damage.base_damage, 1000);
+        assert!((damage.critical_rate - 0.13).abs() < 1e-6);
+        assert_eq!(damage.critical_damage, 2000);
+
+        // 通常平均 (900 + 1100) / 2 = 1000、期待値 = 1000 * 0.87 + 2000 * 0.13 = 1130
+        assert!((damage.average_damage - 1130.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_simulate_single_hit_rolls_critical() {
+        let mut calculator = DamageCalculator::new();
+        let (equipment_set, monster) = critical_test_setup(100.0);
+        let character = Character::new();
+
+        for _ in 0..10 {
+            let damage = calculator.simulate_single_hit(
+                &character,
+                &equipment_set,
+                &Skill::normal_attack(),
+                &monster,
+            );
+            assert_eq!(damage, 1500);
+        }
+    }
+
+    #[test]
+    fn test_character_stats_change_damage_with_same_equipment() {
+        let mut calculator = DamageCalculator::new();
+        let (equipment_set, monster) = critical_test_setup(0.0);
+
+        let stats = Stats {
+            hack: 100,
+            stab: 50,
+            ..Stats::default()
+        };
+        let boris = Character::new()
+            .with_class(100, CharacterClass::Boris)
+            .with_stats(stats)
+            .with_base_attack(300);
+        let sibelin = Character::new()
+            .with_class(100, CharacterClass::Sibelin)
+            .with_stats(stats)
+            .with_base_attack(300);
+
+        // 300 + 100 * 2 + 1100 - 100 = 1500
+        let boris_damage =
+            calculator.calculate_damage(&boris, &equipment_set, &Skill::normal_attack(), &monster);
+        assert_eq!(boris_damage.base_damage, 1500);
+
+        // 300 + 50 * 2 + 1100 - 100 = 1400
+        let sibelin_damage = calculator.calculate_damage(
+            &sibelin,
+            &equipment_set,
+            &Skill::normal_attack(),
+            &monster,
+        );
+        assert_eq!(sibelin_damage.base_damage, 1400);
+    }
+
+    #[test]
+    fn test_skill_multiplier_and_hit_count() {
+        let mut calculator = DamageCalculator::new();
+        let (equipment_set, monster) = critical_test_setup(0.0);
+
+        // 倍率 1.0 + 0.1 * (11 - 1) = 2.0、3ヒット
+        let skill = Skill::new(
+            "test_skill",
+            "テストスキル",
+            1.0,
+            0.1,
+            3,
+            DamageType::Physical,
+        )
+        .with_level(11);
+
+        let damage =
+            calculator.calculate_damage(&Character::new(), &equipment_set, &skill, &monster);
+
+        // 1100 * 2.0 - 100 = 2100
+        assert_eq!(damage.base_damage, 2100);
+        assert_eq!(damage.hit_count, 3);
+        assert!((damage.damage_per_cast - damage.average_damage * 3.0).abs() < 1e-3);
+        assert_eq!(
+            damage.casts_to_kill,
+            (100000.0 / damage.damage_per_cast).ceil() as u64
+        );
+    }
+
+    #[test]
+    fn test_fixed_damage_skill_ignores_defense() {
+        let mut calculator = DamageCalculator::new();
+        let (equipment_set, _) = critical_test_setup(0.0);
+        let monster = Monster::new("boss", "ボス", 40, 100000, 1500, 50000, 9000, 0.9, 120);
+        let skill =
+            Skill::new("fixed", "固定", 1.0, 0.0, 2, DamageType::Fixed).with_fixed_damage(5000);
+
+        let damage =
+            calculator.calculate_damage(&Character::new(), &equipment_set, &skill, &monster);
+
+        assert_eq!(damage.base_damage, 5000);
+        assert_eq!(damage.min_damage, 5000);
+        assert_eq!(damage.max_damage, 5000);
+        assert_eq!(damage.damage_type, DamageType::Fixed);
+        assert_eq!(damage.casts_to_kill, 10);
+    }
+
+    #[test]
+    fn test_typed_options_change_damage() {
+        let mut calculator = DamageCalculator::new();
+        let (mut equipment_set, monster) = critical_test_setup(0.0);
+        let weapon = equipment_set.weapon.as_mut().unwrap();
+        weapon.element = Element::Fire;
+        weapon.options = vec![
+            EquipmentOption {
+                name: "攻撃力+10%".to_string(),
+                value: 10.0,
+            },
+            EquipmentOption {
+                name: "火属性攻撃+5%".to_string(),
+                value: 5.0,
+            },
+            EquipmentOption {
+                name: "全属性攻撃+3%".to_string(),
+                value: 3.0,
+            },
+            EquipmentOption {
+                name: "与ダメージ+10%".to_string(),
+                value: 10.0,
+            },
+        ];
+
+        let damage = calculator.calculate_damage(
+            &Character::new(),
+            &equipment_set,
+            &Skill::normal_attack(),
+            &monster,
+        );
+
+        // (1100 * 1.1 - 100) * 1.08 * 1.1 = 1318.68
+        assert_eq!(damage.base_damage, 1318);
+    }
+
+    #[test]
+    fn test_breakdown_records_each_stage() {
+        let mut calculator = DamageCalculator::new().with_breakdown();
+        let (equipment_set, _) = critical_test_setup(0.0);
+        let monster = Monster::new("boss", "ボス", 40, 100000, 100, 2000, 0, 0.5, 0);
+
+        let damage = calculator.calculate_damage(
+            &Character::new(),
+            &equipment_set,
+            &Skill::normal_attack(),
+            &monster,
+        );
+        let breakdown = damage.breakdown.expect("breakdown should be recorded");
+
+        let kinds: Vec<_> = breakdown.iter().map(|stage| stage.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                DamageStageKind::SkillMultiplier,
+                DamageStageKind::StatusDefense,
+                DamageStageKind::FixedDefense,
+                DamageStageKind::FixedReduction,
+                DamageStageKind::CutRate,
+                DamageStageKind::ElementBonus,
+                DamageStageKind::DamageCategory(DamageCategory::ElementAttack),
+                DamageStageKind::DamageCategory(DamageCategory::DamageIncrease),
+                DamageStageKind::DamageCategory(DamageCategory::TypeDamage),
+                DamageStageKind::DamageCategory(DamageCategory::SkillDamage),
+                DamageStageKind::LevelCorrection,
+            ]
+        );
+
+        // 1100 - 100 = 1000
+        assert_eq!(breakdown[1].output, 1000.0);
+        assert!(!breakdown[1].floored);
+        // 1000 - 2000 → 最低ダメージ1
+        assert_eq!(breakdown[2].input, 1000.0);
+        assert_eq!(breakdown[2].output, 1.0);
+        assert!(breakdown[2].floored);
+        // 1 * 0.5 = 0.5 → 切り捨て後に最低ダメージ1
+        assert!(breakdown[10].floored);
+        assert_eq!(breakdown[10].output, 1.0);
+        assert_eq!(damage.base_damage, 1);
+    }
+
+    #[test]
+    fn test_breakdown_is_disabled_by_default() {
+        let mut calculator = DamageCalculator::new();
+        let (equipment_set, monster) = critical_test_setup(0.0);
+
+        let damage = calculator.calculate_damage(
+            &Character::new(),
+            &equipment_set,
+            &Skill::normal_attack(),
+            &monster,
+        );
+
+        assert!(damage.breakdown.is_none());
+    }
+
+    #[test]
+    fn test_seeded_simulation_is_reproducible() {
+        let (equipment_set, monster) = critical_test_setup(30.0);
+        let character = Character::new();
+        let skill = Skill::normal_attack();
+
+        let mut first = DamageCalculator::with_seed(12345);
+        let mut second = DamageCalculator::with_seed(12345);
+
+        let first_result =
+            first.simulate_multiple_hits(&character, &equipment_set, &skill, &monster, 100);
+        let second_result =
+            second.simulate_multiple_hits(&character, &equipment_set, &skill, &monster, 100);
+
+        assert_eq!(first_result.seed, 12345);
+        assert_eq!(first_result.total_damage, second_result.total_damage);
+        // 乱数生成器やその使い方が変わると、共有されたシードの結果が再現できなくなる
+        assert_eq!(first_result.total_damage, 116359);
+        for _ in 0..10 {
+            assert_eq!(
+                first.simulate_single_hit(&character, &equipment_set, &skill, &monster),
+                second.simulate_single_hit(&character, &equipment_set, &skill, &monster)
+            );
+        }
+    }
+
+    #[test]
+    fn test_simulation_report_statistics() {
+        let mut calculator = DamageCalculator::with_seed(7);
+        let (equipment_set, monster) = critical_test_setup(20.0);
+
+        let report = calculator.simulate_multiple_hits(
+            &Character::new(),
+            &equipment_set,
+            &Skill::normal_attack(),
+            &monster,
+            2000,
+        );
+
+        // 通常 900~1100、クリティカル 1500
+        assert_eq!(report.hits, 2000);
+        assert!(report.min_damage >= 900);
+        assert_eq!(report.max_damage, 1500);
+        assert!(report.p5 <= report.p50 && report.p50 <= report.p95);
+        assert_eq!(report.p95, 1500);
+        assert!(report.critical_count > 300 && report.critical_count < 500);
+        assert!(report.std_dev > 0.0);
+        assert_eq!(
+            report.histogram.iter().map(|bin| bin.count).sum::<u32>(),
+            2000
+        );
+        assert_eq!(
+            report.hits_to_kill.iter().map(|k| k.count).sum::<u32>(),
+            report.kills
+        );
+    }
+
+    #[test]
+    fn test_simulated_hits_are_capped() {
+        let mut calculator = DamageCalculator::with_seed(7);
+        let (equipment_set, monster) = critical_test_setup(0.0);
+
+        let report = calculator.simulate_multiple_hits(
+            &Character::new(),
+            &equipment_set,
+            &Skill::normal_attack(),
+            &monster,
+            u32::MAX,
+        );
+        assert_eq!(report.hits, MAX_SIMULATED_HITS);
+    }
+
+    #[test]
+    fn test_dps_and_time_to_kill() {
+        let mut calculator = DamageCalculator::new();
+        let (equipment_set, monster) = critical_test_setup(0.0);
+        let character = Character::new().with_attack_speed(2.0);
+
+        // 通常攻撃: 平均1000ダメージ、0.5秒ごと、100000 / 1000 = 100ヒット
+        let damage = calculator.calculate_damage(
+            &character,
+            &equipment_set,
+            &Skill::normal_attack(),
+            &monster,
+        );
+        assert!((damage.cycle_time - 0.5).abs() < 1e-6);
+        assert!((damage.dps - 2000.0).abs() < 1e-2);
+        assert!((damage.time_to_kill - 50.0).abs() < 1e-3);
+        assert_eq!(damage.time_to_kill_text, "50秒");
+
+        // スキル: 3ヒット、クールタイム4秒（詠唱0.5秒）
+        let skill = Skill::new(
+            "test_skill",
+            "テストスキル",
+            1.0,
+            0.0,
+            3,
+            DamageType::Physical,
+        )
+        .with_timing(4.0, 0.5);
+        let damage = calculator.calculate_damage(&character, &equipment_set, &skill, &monster);
+        assert!((damage.cycle_time - 4.0).abs() < 1e-6);
+        assert!((damage.dps - 750.0).abs() < 1e-2);
+        // 100000 / 3000 → 34回、33回 × 4秒 + 最後の詠唱0.5秒 = 132.5秒
+        assert_eq!(damage.casts_to_kill, 34);
+        assert!((damage.time_to_kill - 132.5).abs() < 1e-3);
+        assert_eq!(damage.time_to_kill_text, "2分13秒");
+
+        // 1回で倒せるならクールタイムを待たずに詠唱の終わりで撃破
+        let monster = Monster::new("dummy", "ダミー", 1, 1000, 0, 0, 0, 0.0, 0);
+        let skill = skill.with_timing(10.0, 0.5);
+        let damage = calculator.calculate_damage(&character, &equipment_set, &skill, &monster);
+        assert_eq!(damage.casts_to_kill, 1);
+        assert!((damage.time_to_kill - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_buffs_apply_to_both_sides() {
+        let (equipment_set, _) = critical_test_setup(0.0);
+        let monster = Monster::new("boss", "ボス", 40, 100000, 1000, 2000, 0, 0.5, 0);
+        let buffs = vec![
+            Buff::new("might", "マイト", BuffStat::AttackPercent, 100.0, 30.0),
+            Buff::new(
+                "guard_break",
+                "防御ダウン",
+                BuffStat::FixedDefense,
+                50.0,
+                30.0,
+            ),
+            Buff::new("cut_down", "カット率ダウン", BuffStat::CutRate, 30.0, 30.0),
+            Buff::new("focus", "集中", BuffStat::CriticalRate, 25.0, 30.0),
+        ];
+        let mut calculator = DamageCalculator::new().with_buffs(buffs);
+
+        let damage = calculator.calculate_damage(
+            &Character::new(),
+            &equipment_set,
+            &Skill::normal_attack(),
+            &monster,
+        );
+
+        // (1100 * 2 - 1000 - 1000) * (1 - 0.2) = 160
+        assert_eq!(damage.base_damage, 160);
+        assert!((damage.critical_rate - 0.25).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_penetration_against_endgame_boss() {
+        let mut calculator = DamageCalculator::new().with_breakdown();
+        let (mut equipment_set, _) = critical_test_setup(0.0);
+        let monster = create_default_monsters()
+            .find_by_id("odein")
+            .unwrap()
+            .clone();
+        let weapon = equipment_set.weapon.as_mut().unwrap();
+        weapon.attack = 70000;
+        weapon.options = vec![EquipmentOption {
+            name: "固定防御無視+50%".to_string(),
+            value: 50.0,
+        }];
+        let skill = Skill::normal_attack().with_penetration(Penetration {
+            cut_rate: 20.0,
+            ..Penetration::default()
+        });
+
+        let damage =
+            calculator.calculate_damage(&Character::new(), &equipment_set, &skill, &monster);
+
+        // 70000 - 1500 - 51720 * 0.5 - 9285 = 33355
+        // カット率 0.51 * 0.8 = 0.408 → 33355 * 0.592 = 19746.16
+        assert_eq!(damage.base_damage, 19746);
+        let breakdown = damage.breakdown.unwrap();
+        assert_eq!(breakdown[2].modifier, 25860.0);
+        assert_eq!(breakdown[2].penetration, 50.0);
+        assert_eq!(breakdown[4].penetration, 20.0);
+    }
+
+    #[test]
+    fn test_damage_categories_from_options_skill_and_buffs() {
+        let (mut equipment_set, monster) = critical_test_setup(0.0);
+        equipment_set.weapon.as_mut().unwrap().options = vec![EquipmentOption {
+            name: "与ダメージ+10%".to_string(),
+            value: 10.0,
+        }];
+        let skill = Skill::new("slash", "スラッシュ", 1.0, 0.0, 1, DamageType::Physical)
+            .with_damage_modifier(DamageModifier::new(DamageCategory::DamageIncrease, 10.0));
+        let buffs = vec![Buff::new(
+            "focus",
+            "集中",
+            BuffStat::Damage(DamageCategory::SkillDamage),
+            20.0,
+            10.0,
+        )];
+        let mut calculator = DamageCalculator::new().with_breakdown().with_buffs(buffs);
+
+        let damage =
+            calculator.calculate_damage(&Character::new(), &equipment_set, &skill, &monster);
+
+        // (1100 - 100) × (1 + 0.1 + 0.1) × (1 + 0.2) = 1440
+        assert_eq!(damage.base_damage, 1440);
+        let breakdown = damage.breakdown.unwrap();
+        let multiplier = |category| {
+            breakdown
+                .iter()
+                .find(|stage| stage.kind == DamageStageKind::DamageCategory(category))
+                .unwrap()
+                .modifier
+        };
+        assert!((multiplier(DamageCategory::DamageIncrease) - 1.2).abs() < 1e-6);
+        assert!((multiplier(DamageCategory::SkillDamage) - 1.2).abs() < 1e-6);
+        assert_eq!(multiplier(DamageCategory::TypeDamage), 1.0);
+    }
+
+    #[test]
+    fn test_magic_path_uses_magic_attack_and_resist() {
+        let mut calculator = DamageCalculator::new().with_breakdown();
+        let (equipment_set, monster) = critical_test_setup(0.0);
+        let monster = monster.with_magic_resist(600);
+        let caster = Character::new()
+            .with_class(1, CharacterClass::Tichel)
+            .with_stats(Stats {
+                int: 500,
+                ..Stats::default()
+            });
+        let magic = Skill::new("bolt", "ボルト", 1.0, 0.0, 1, DamageType::Magic);
+
+        let damage = calculator.calculate_damage(&caster, &equipment_set, &magic, &monster);
+
+        // 魔法攻撃力 500 × 2 + 1100 = 2100、魔法防御 600 を引いて 1500
+        assert_eq!(damage.damage_type, DamageType::Magic);
+        assert_eq!(damage.attack, 2100);
+        assert_eq!(damage.base_damage, 1500);
+        let breakdown = damage.breakdown.unwrap();
+        assert_eq!(breakdown[1].kind, DamageStageKind::MagicResist);
+
+        // 物理攻撃はステータス防御で計算する
+        let physical =
+            calculator.calculate_damage(&caster, &equipment_set, &Skill::normal_attack(), &monster);
+        assert_eq!(physical.damage_type, DamageType::Physical);
+        assert_eq!(physical.base_damage, 1000);
+    }
+
+    #[test]
+    fn test_true_damage_skips_defense_but_keeps_cut_rate() {
+        let mut calculator = DamageCalculator::new().with_breakdown();
+        let (equipment_set, _) = critical_test_setup(0.0);
+        let monster = Monster::new("boss", "ボス", 40, 100000, 100, 2000, 500, 0.5, 0);
+        let skill = Skill::new("true", "真撃", 1.0, 0.0, 1, DamageType::True);
+
+        let damage =
+            calculator.calculate_damage(&Character::new(), &equipment_set, &skill, &monster);
+
+        // 防御はすべて無視し、カット率50%だけ適用: 1100 × 0.5 = 550
+        assert_eq!(damage.damage_type, DamageType::True);
+        assert_eq!(damage.base_damage, 550);
+        let breakdown = damage.breakdown.unwrap();
+        assert!(breakdown[1].bypassed && breakdown[2].bypassed && breakdown[3].bypassed);
+        assert!(!breakdown[4].bypassed);
+    }
+
+    #[test]
+    fn test_bypass_from_skill_and_option() {
+        let mut calculator = DamageCalculator::new();
+        let (mut equipment_set, _) = critical_test_setup(0.0);
+        let monster = Monster::new("boss", "ボス", 40, 100000, 100, 500, 0, 0.5, 0);
+
+        // スキル指定: ステータス防御を無視 → (1100 - 500) × 0.5 = 300
+        let skill = Skill::normal_attack().with_bypass(DamageStageKind::StatusDefense);
+        let damage =
+            calculator.calculate_damage(&Character::new(), &equipment_set, &skill, &monster);
+        assert_eq!(damage.base_damage, 300);
+
+        // オプション指定: カット率を無視 → 1100 - 100 - 500 = 500
+        equipment_set.weapon.as_mut().unwrap().options = vec![EquipmentOption {
+            name: "カット率無効".to_string(),
+            value: 0.0,
+        }];
+        let damage = calculator.calculate_damage(
+            &Character::new(),
+            &equipment_set,
+            &Skill::normal_attack(),
+            &monster,
+        );
+        assert_eq!(damage.base_damage, 500);
+    }
+
+    #[test]
+    fn test_misses_reduce_average_damage() {
+        let mut calculator = DamageCalculator::with_seed(5);
+        let (equipment_set, monster) = critical_test_setup(0.0);
+        let monster = monster.with_evasion(200);
+        let character = Character::new().with_accuracy(100);
+        let skill = Skill::normal_attack();
+
+        let damage = calculator.calculate_damage(&character, &equipment_set, &skill, &monster);
+
+        // 命中率 100 / 200 = 50%、平均ダメージは 1000 × 0.5
+        assert_eq!(damage.hit_chance, 0.5);
+        assert!((damage.average_damage - 500.0).abs() < 1e-3);
+        assert_eq!(damage.hits_to_kill, 200);
+
+        let simulation =
+            calculator.simulate_multiple_hits(&character, &equipment_set, &skill, &monster, 2000);
+        assert!((800..1200).contains(&simulation.miss_count));
+        assert_eq!(simulation.min_damage, 0);
+    }
+
+    #[test]
+    fn test_level_correction_stage() {
+        let (equipment_set, monster) = critical_test_setup(0.0);
+        let mut calculator = DamageCalculator::new()
+            .with_breakdown()
+            .with_level_correction(LevelCorrection::new(&[(-10, 0.5), (0, 1.0)]));
+        let skill = Skill::normal_attack();
+
+        // レベル5 対 レベル10 → 倍率 0.75
+        let character = Character::new().with_class(5, CharacterClass::Lucian);
+        let damage = calculator.calculate_damage(&character, &equipment_set, &skill, &monster);
+        assert_eq!(damage.base_damage, 750);
+        let stage = damage.breakdown.unwrap().pop().unwrap();
+        assert_eq!(stage.kind, DamageStageKind::LevelCorrection);
+        assert_eq!(stage.modifier, 0.75);
+
+        // 同レベルなら補正なし
+        let character = Character::new().with_class(10, CharacterClass::Lucian);
+        let damage = calculator.calculate_damage(&character, &equipment_set, &skill, &monster);
+        assert_eq!(damage.base_damage, 1000);
+    }
+
+    #[test]
+    fn test_endgame_values_exceed_u32() {
+        let mut calculator = DamageCalculator::new();
+        let (mut equipment_set, _) = critical_test_setup(0.0);
+        equipment_set.weapon.as_mut().unwrap().attack = u32::MAX;
+        let character = Character::new()
+            .with_base_attack(u32::MAX)
+            .with_critical(0.5, 3.0);
+        let skill = Skill::new(
+            "finisher",
+            "フィニッシャー",
+            1000.0,
+            0.0,
+            20,
+            DamageType::Physical,
+        );
+        let monster = Monster::new("raid", "レイドボス", 99, u64::MAX, 0, 0, 0, 0.0, 0);
+
+        let damage = calculator.calculate_damage(&character, &equipment_set, &skill, &monster);
+
+        // (u32::MAX × 2) × 1000 は u32 を大きく超えるが正確に計算される
+        let expected = (u32::MAX as u64 * 2) * 1000;
+        assert_eq!(damage.attack, u32::MAX as u64 * 2);
+        assert_eq!(damage.base_damage, expected);
+        assert_eq!(damage.critical_damage, expected * 3);
+        assert!(damage.max_damage > damage.base_damage);
+        assert!(damage.hits_to_kill > 0);
+        assert!(damage.damage_per_cast > damage.average_damage);
+    }
+
+    #[test]
+    fn test_damage_saturates_at_u64_max() {
+        let mut calculator = DamageCalculator::with_seed(1);
+        let (equipment_set, monster) = critical_test_setup(0.0);
+        let character = Character::new().with_critical(1.0, 3.0);
+        let skill = Skill::new(
+            "overflow",
+            "オーバーフロー",
+            f32::MAX,
+            0.0,
+            1,
+            DamageType::Physical,
+        );
+
+        let damage = calculator.calculate_damage(&character, &equipment_set, &skill, &monster);
+        assert_eq!(damage.base_damage, u64::MAX);
+        assert_eq!(damage.critical_damage, u64::MAX);
+        assert_eq!(damage.max_damage, u64::MAX);
+        assert_eq!(damage.hits_to_kill, 1);
+
+        let cast = calculator.simulate_cast(&character, &equipment_set, &skill, &monster);
+        assert_eq!(cast, u64::MAX);
+
+        // JavaScriptへは Number.MAX_SAFE_INTEGER に丸めて返す
+        let json = serde_json::to_value(&damage).unwrap();
+        assert_eq!(json["max_damage"], MAX_SAFE_INTEGER);
+        assert_eq!(json["hits_to_kill"], 1);
+    }
 }
@@ -0,0 +1,166 @@
+use super::element::Element;
+use super::option_effect::{DamageIncreaseKind, OptionTotals};
+use super::skill::{DamageType, Skill};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// ダメージ増加の区分
+///
+/// 同じ区分の補正は加算し、区分同士は乗算する。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum DamageCategory {
+    ElementAttack,  // 属性攻撃%
+    DamageIncrease, // 与ダメージ%
+    TypeDamage,     // 物理・魔法ダメージ%
+    SkillDamage,    // スキルダメージ%
+}
+
+impl DamageCategory {
+    /// 計算で適用する順番の全区分
+    pub const ALL: [DamageCategory; 4] = [
+        DamageCategory::ElementAttack,
+        DamageCategory::DamageIncrease,
+        DamageCategory::TypeDamage,
+        DamageCategory::SkillDamage,
+    ];
+}
+
+/// 区分つきのダメージ補正（スキル・バフ用）
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DamageModifier {
+    pub category: DamageCategory,
+    pub value: f32, // 補正値%
+}
+
+impl DamageModifier {
+    /// 新しいダメージ補正を作成
+    pub fn new(category: DamageCategory, value: f32) -> Self {
+        Self { category, value }
+    }
+}
+
+/// 区分ごとに合計したダメージ補正%
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DamageModifiers {
+    percents: BTreeMap<DamageCategory, f32>,
+}
+
+impl DamageModifiers {
+    /// 新しい空の補正を作成
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 装備オプションの補正を、スキルの種類と属性に応じて区分へ振り分ける
+    pub fn from_options(options: &OptionTotals, skill: &Skill, element: Element) -> Self {
+        let type_kind = match skill.damage_type {
+            DamageType::Magic => DamageIncreaseKind::Magic,
+            _ => DamageIncreaseKind::Physical,
+        };
+
+        let mut modifiers = Self::new();
+        modifiers.add(
+            DamageCategory::ElementAttack,
+            options.element_attack_percent(element),
+        );
+        modifiers.add(
+            DamageCategory::DamageIncrease,
+            options.damage_increase_percent(DamageIncreaseKind::All),
+        );
+        modifiers.add(
+            DamageCategory::TypeDamage,
+            options.damage_increase_percent(type_kind),
+        );
+        if !skill.is_normal_attack() {
+            modifiers.add(
+                DamageCategory::SkillDamage,
+                options.damage_increase_percent(DamageIncreaseKind::Skill),
+            );
+        }
+        modifiers
+    }
+
+    /// 補正%を区分に加算
+    pub fn add(&mut self, category: DamageCategory, percent: f32) {
+        *self.percents.entry(category).or_default() += percent;
+    }
+
+    /// 区分つきの補正をまとめて加算
+    pub fn extend<'a>(&mut self, modifiers: impl IntoIterator<Item = &'a DamageModifier>) {
+        for modifier in modifiers {
+            self.add(modifier.category, modifier.value);
+        }
+    }
+
+    /// 別の補正をまとめて加算
+    pub fn extend_from(&mut self, other: &DamageModifiers) {
+        for (&category, &percent) in &other.percents {
+            self.add(category, percent);
+        }
+    }
+
+    /// 区分の補正を取り除く
+    pub fn remove(&mut self, category: DamageCategory) {
+        self.percents.remove(&category);
+    }
+
+    /// 区分の補正%の合計
+    pub fn percent(&self, category: DamageCategory) -> f32 {
+        self.percents.get(&category).copied().unwrap_or(0.0)
+    }
+
+    /// 区分の倍率（1 + 合計% / 100、最低0）
+    pub fn multiplier(&self, category: DamageCategory) -> f32 {
+        (1.0 + self.percent(category) / 100.0).max(0.0)
+    }
+
+    /// 全区分の補正%の合計（区分を問わず加算する場合）
+    pub fn total_percent(&self) -> f32 {
+        self.percents.values().sum()
+    }
+
+    /// 全区分の倍率の積
+    pub fn total_multiplier(&self) -> f32 {
+        DamageCategory::ALL
+            .iter()
+            .map(|&category| self.multiplier(category))
+            .product()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::option_effect::OptionEffect;
+
+    #[test]
+    fn test_additive_within_and_multiplicative_across_categories() {
+        let mut options = OptionTotals::default();
+        options.add(&OptionEffect::parse("全属性攻撃+3%", 3.0));
+        options.add(&OptionEffect::parse("火属性攻撃+5%", 5.0));
+        options.add(&OptionEffect::parse("与ダメージ+10%", 10.0));
+
+        let skill = Skill::normal_attack();
+        let mut modifiers = DamageModifiers::from_options(&options, &skill, Element::Fire);
+        modifiers.extend(&[DamageModifier::new(DamageCategory::DamageIncrease, 10.0)]);
+
+        // 属性攻撃 3% + 5% = 8%、与ダメージ 10% + 10% = 20%
+        assert!((modifiers.multiplier(DamageCategory::ElementAttack) - 1.08).abs() < 1e-6);
+        assert!((modifiers.multiplier(DamageCategory::DamageIncrease) - 1.2).abs() < 1e-6);
+        assert!((modifiers.total_multiplier() - 1.08 * 1.2).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_skill_damage_only_applies_to_skills() {
+        let mut options = OptionTotals::default();
+        options.add(&OptionEffect::parse("スキルダメージ+8%", 8.0));
+
+        let normal =
+            DamageModifiers::from_options(&options, &Skill::normal_attack(), Element::None);
+        assert_eq!(normal.percent(DamageCategory::SkillDamage), 0.0);
+
+        let skill = Skill::new("slash", "スラッシュ", 2.0, 0.0, 1, DamageType::Physical);
+        let skill = DamageModifiers::from_options(&options, &skill, Element::None);
+        assert_eq!(skill.percent(DamageCategory::SkillDamage), 8.0);
+    }
+}
@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+
+/// 有利属性の相性倍率
+pub const ADVANTAGE_MULTIPLIER: f32 = 1.5;
+/// 不利属性の相性倍率
+pub const DISADVANTAGE_MULTIPLIER: f32 = 0.5;
+
+/// 属性の種類
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Element {
+    #[default]
+    None, // 無属性
+    Fire,  // 火
+    Water, // 水
+    Wind,  // 風
+    Earth, // 地
+    Light, // 光
+    Dark,  // 闇
+}
+
+impl Element {
+    /// 無属性を除くすべての属性
+    pub const ALL: [Element; 6] = [
+        Element::Fire,
+        Element::Water,
+        Element::Wind,
+        Element::Earth,
+        Element::Light,
+        Element::Dark,
+    ];
+
+    /// この属性が有利を取る属性
+    fn strong_against(self) -> Option<Element> {
+        match self {
+            Element::Fire => Some(Element::Wind),
+            Element::Wind => Some(Element::Earth),
+            Element::Earth => Some(Element::Water),
+            Element::Water => Some(Element::Fire),
+            Element::Light => Some(Element::Dark),
+            Element::Dark => Some(Element::Light),
+            Element::None => None,
+        }
+    }
+
+    /// 攻撃側属性と防御側属性の相性倍率
+    pub fn affinity(self, defender: Element) -> f32 {
+        if self == Element::None || defender == Element::None {
+            1.0
+        } else if self.strong_against() == Some(defender) {
+            ADVANTAGE_MULTIPLIER
+        } else if defender.strong_against() == Some(self) {
+            DISADVANTAGE_MULTIPLIER
+        } else {
+            1.0
+        }
+    }
+
+    /// 属性値と属性耐性値から属性ボーナスを計算
+    ///
+    /// 相性倍率を掛けた属性値が耐性値を上回った分を100で割った値を返す。
+    /// 無属性攻撃や耐性値を下回る場合のボーナスは0。
+    pub fn bonus(self, element_value: u32, defender: Element, resistance: u32) -> f32 {
+        if self == Element::None {
+            return 0.0;
+        }
+
+        let effective = element_value as f32 * self.affinity(defender);
+        ((effective - resistance as f32) / 100.0).max(0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_affinity_chart() {
+        assert_eq!(Element::Fire.affinity(Element::Wind), ADVANTAGE_MULTIPLIER);
+        assert_eq!(
+            Element::Fire.affinity(Element::Water),
+            DISADVANTAGE_MULTIPLIER
+        );
+        assert_eq!(Element::Light.affinity(Element::Dark), ADVANTAGE_MULTIPLIER);
+        assert_eq!(Element::Dark.affinity(Element::Light), ADVANTAGE_MULTIPLIER);
+        assert_eq!(Element::Fire.affinity(Element::Light), 1.0);
+        assert_eq!(Element::None.affinity(Element::Fire), 1.0);
+    }
+
+    #[test]
+    fn test_element_bonus() {
+        // 200 * 1.5 - 120 = 180 → 1.8
+        assert!((Element::Fire.bonus(200, Element::Wind, 120) - 1.8).abs() < 1e-6);
+        // 300 * 0.5 - 120 = 30 → 0.3
+        assert!((Element::Fire.bonus(300, Element::Water, 120) - 0.3).abs() < 1e-6);
+        // 耐性値を下回る場合はボーナスなし
+        assert_eq!(Element::Fire.bonus(200, Element::Water, 120), 0.0);
+        // 無属性攻撃はボーナスなし
+        assert_eq!(Element::None.bonus(500, Element::Water, 0), 0.0);
+    }
+}
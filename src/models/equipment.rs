@@ -1,4 +1,7 @@
+use super::element::Element;
+use super::option_effect::{OptionEffect, OptionTotals};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// 装備の種類
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -17,6 +20,8 @@ pub struct Equipment {
     pub attack: u32,
     pub defense: u32,
     pub element_value: u32,
+    #[serde(default)]
+    pub element: Element, // 属性の種類
     pub options: Vec<EquipmentOption>,
 }
 
@@ -27,8 +32,15 @@ pub struct EquipmentOption {
     pub value: f32,
 }
 
+impl EquipmentOption {
+    /// オプション文字列を効果に変換
+    pub fn effect(&self) -> OptionEffect {
+        OptionEffect::parse(&self.name, self.value)
+    }
+}
+
 /// キャラクター装備セット
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct EquipmentSet {
     pub weapon: Option<Equipment>,
     pub armor: Option<Equipment>,
@@ -49,44 +61,222 @@ impl EquipmentSet {
         }
     }
 
+    /// 装着中の装備を列挙
+    pub fn equipments(&self) -> impl Iterator<Item = &Equipment> {
+        [
+            &self.weapon,
+            &self.armor,
+            &self.accessory1,
+            &self.accessory2,
+            &self.special,
+        ]
+        .into_iter()
+        .flatten()
+    }
+
+    /// 武器を取得（装備していなければ攻撃力0の武器を装備する）
+    pub fn weapon_mut(&mut self) -> &mut Equipment {
+        self.weapon.get_or_insert_with(|| Equipment {
+            name: "武器なし".to_string(),
+            equipment_type: EquipmentType::Weapon,
+            attack: 0,
+            defense: 0,
+            element_value: 0,
+            element: Element::None,
+            options: vec![],
+        })
+    }
+
+    /// 全スロットのステータスを集計
+    pub fn stat_totals(&self) -> StatTotals {
+        let mut totals = StatTotals::default();
+        for equipment in self.equipments() {
+            totals.add(equipment);
+        }
+        totals
+    }
+
     /// 総合攻撃力を計算
-    pub fn total_attack(&self) -> u32 {
-        let mut total = 0;
+    pub fn total_attack(&self) -> u64 {
+        self.stat_totals().attack
+    }
 
+    /// 総合防御力を計算
+    pub fn total_defense(&self) -> u64 {
+        self.stat_totals().defense
+    }
+
+    /// 全装備のオプション効果を合計
+    pub fn option_totals(&self) -> OptionTotals {
+        self.stat_totals().options
+    }
+
+    /// 攻撃属性を取得（武器の属性）
+    pub fn element(&self) -> Element {
         if let Some(weapon) = &self.weapon {
-            total += weapon.attack;
+            weapon.element
+        } else {
+            Element::None
         }
-        if let Some(armor) = &self.armor {
-            total += armor.attack;
+    }
+
+    /// 攻撃属性の属性値を取得
+    pub fn element_value(&self) -> u32 {
+        self.stat_totals().element_value(self.element())
+    }
+}
+
+/// 装備セット全体のステータス合計
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StatTotals {
+    #[serde(serialize_with = "crate::utils::serialize_js_number")]
+    pub attack: u64,
+    #[serde(serialize_with = "crate::utils::serialize_js_number")]
+    pub defense: u64,
+    pub element_values: HashMap<Element, u32>, // 属性ごとの属性値
+    pub options: OptionTotals,
+}
+
+impl StatTotals {
+    /// 装備1つ分のステータスを加算
+    pub fn add(&mut self, equipment: &Equipment) {
+        self.attack = self.attack.saturating_add(equipment.attack as u64);
+        self.defense = self.defense.saturating_add(equipment.defense as u64);
+        let value = self.element_values.entry(equipment.element).or_default();
+        *value = value.saturating_add(equipment.element_value);
+        for option in &equipment.options {
+            self.options.add(&option.effect());
         }
-        if let Some(acc1) = &self.accessory1 {
-            total += acc1.attack;
+    }
+
+    /// 指定した属性で攻撃するときの属性値
+    ///
+    /// 無属性の装備の属性値はどの属性にも加算される。
+    pub fn element_value(&self, element: Element) -> u32 {
+        let neutral = self
+            .element_values
+            .get(&Element::None)
+            .copied()
+            .unwrap_or(0);
+        if element == Element::None {
+            return neutral;
         }
-        if let Some(acc2) = &self.accessory2 {
-            total += acc2.attack;
+        neutral.saturating_add(self.element_values.get(&element).copied().unwrap_or(0))
+    }
+}
+
+// テスト用の装備を作る補助関数（各モジュールのテストで共通に使う）
+#[cfg(test)]
+impl Equipment {
+    /// テスト用の装備（防御力・属性値は0、オプションなし）
+    pub(crate) fn test_item(name: &str, equipment_type: EquipmentType, attack: u32) -> Self {
+        Self {
+            name: name.to_string(),
+            equipment_type,
+            attack,
+            defense: 0,
+            element_value: 0,
+            element: Element::None,
+            options: vec![],
         }
-        if let Some(special) = &self.special {
-            total += special.attack;
+    }
+
+    /// テスト用の武器
+    pub(crate) fn test_weapon(attack: u32, element: Element) -> Self {
+        Self {
+            element,
+            ..Self::test_item("テスト武器", EquipmentType::Weapon, attack)
         }
+    }
 
-        total
+    /// オプションを追加
+    pub(crate) fn with_test_option(mut self, name: &str, value: f32) -> Self {
+        self.options.push(EquipmentOption {
+            name: name.to_string(),
+            value,
+        });
+        self
     }
+}
 
-    /// 総合防御力を計算
-    pub fn total_defense(&self) -> u32 {
-        if let Some(weapon) = &self.weapon {
-            weapon.defense
-        } else {
-            0
+#[cfg(test)]
+impl EquipmentSet {
+    /// テスト用の武器だけを装備したセット
+    pub(crate) fn test_weapon(attack: u32, element: Element) -> Self {
+        Self {
+            weapon: Some(Equipment::test_weapon(attack, element)),
+            ..Self::new()
         }
     }
+}
 
-    /// 属性値を取得
-    pub fn element_value(&self) -> u32 {
-        if let Some(weapon) = &self.weapon {
-            weapon.element_value
-        } else {
-            0
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn equipment(
+        equipment_type: EquipmentType,
+        attack: u32,
+        defense: u32,
+        element: Element,
+        element_value: u32,
+    ) -> Equipment {
+        Equipment {
+            name: "テスト装備".to_string(),
+            equipment_type,
+            attack,
+            defense,
+            element_value,
+            element,
+            options: vec![EquipmentOption {
+                name: "クリティカル率+1%".to_string(),
+                value: 1.0,
+            }],
         }
     }
+
+    #[test]
+    fn test_stat_totals_include_every_slot() {
+        let equipment_set = EquipmentSet {
+            weapon: Some(equipment(EquipmentType::Weapon, 100, 0, Element::Fire, 20)),
+            armor: Some(equipment(EquipmentType::Armor, 0, 80, Element::None, 0)),
+            accessory1: Some(equipment(EquipmentType::Accessory, 5, 5, Element::Fire, 10)),
+            accessory2: Some(equipment(
+                EquipmentType::Accessory,
+                0,
+                10,
+                Element::Wind,
+                10,
+            )),
+            special: Some(equipment(EquipmentType::Special, 20, 20, Element::None, 20)),
+        };
+
+        let totals = equipment_set.stat_totals();
+        assert_eq!(totals.attack, 125);
+        assert_eq!(totals.defense, 115);
+        assert_eq!(totals.element_value(Element::Fire), 50);
+        assert_eq!(totals.element_value(Element::Wind), 30);
+        assert!((totals.options.critical_rate - 5.0).abs() < 1e-6);
+
+        assert_eq!(equipment_set.total_defense(), 115);
+        assert_eq!(equipment_set.element_value(), 50);
+    }
+
+    #[test]
+    fn test_stat_totals_do_not_overflow() {
+        let max =
+            |equipment_type| equipment(equipment_type, u32::MAX, u32::MAX, Element::Fire, u32::MAX);
+        let equipment_set = EquipmentSet {
+            weapon: Some(max(EquipmentType::Weapon)),
+            armor: Some(max(EquipmentType::Armor)),
+            accessory1: Some(max(EquipmentType::Accessory)),
+            accessory2: Some(max(EquipmentType::Accessory)),
+            special: Some(max(EquipmentType::Special)),
+        };
+
+        assert_eq!(equipment_set.total_attack(), u32::MAX as u64 * 5);
+        assert_eq!(equipment_set.total_defense(), u32::MAX as u64 * 5);
+        // 属性値は u32 の範囲に飽和
+        assert_eq!(equipment_set.element_value(), u32::MAX);
+    }
 }
@@ -0,0 +1,188 @@
+use super::damage::{DamagePipeline, DamageStageKind};
+use super::damage_category::{DamageCategory, DamageModifiers};
+use super::penetration::Penetration;
+use std::fmt::Debug;
+
+/// 現行の計算式のバージョン名
+pub const CURRENT_FORMULA: &str = "current";
+/// 選択できる計算式のバージョン名
+///
+/// 以前の計算式は、出典のある資料で確認できたものだけを追加する。
+pub const FORMULA_VERSIONS: [&str; 1] = [CURRENT_FORMULA];
+
+/// 計算式に渡す、バフ・装備・スキルを反映済みの値
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormulaInput {
+    pub skill_multiplier: f32,
+    pub defense_stage: DamageStageKind, // ステータス防御か魔法防御か
+    pub defense: u32,
+    pub fixed_defense: u32,
+    pub fixed_reduction: u32,
+    pub cut_rate: f32,
+    pub penetration: Penetration,
+    pub element_bonus: f32,
+    pub modifiers: DamageModifiers,
+    pub level_multiplier: f32, // レベル差補正の倍率
+}
+
+/// ダメージ計算式
+///
+/// 攻撃力から始まる計算過程に、スキル倍率・防御・カット率などの段階を順に適用する。
+/// ゲームのパッチで計算式が変わったときは、新しいバージョンとして実装を追加する。
+pub trait DamageFormula: Debug {
+    /// 計算式のバージョン名
+    fn version(&self) -> &'static str;
+
+    /// 計算過程に各段階を適用
+    fn apply(&self, input: &FormulaInput, pipeline: &mut DamagePipeline);
+}
+
+/// 現行の計算式
+///
+/// スキル倍率 → 防御の減算（3段階） → カット率 → 属性ボーナス → ダメージ増加の区分ごとの乗算 → レベル差補正
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CurrentFormula;
+
+impl DamageFormula for CurrentFormula {
+    fn version(&self) -> &'static str {
+        CURRENT_FORMULA
+    }
+
+    fn apply(&self, input: &FormulaInput, pipeline: &mut DamagePipeline) {
+        let penetration = &input.penetration;
+
+        pipeline.multiply(DamageStageKind::SkillMultiplier, input.skill_multiplier);
+        pipeline.truncate();
+
+        // ステータス防御（魔法防御）・固定防御・固定減少（各段階で貫通後の防御値を引き、最低ダメージは1）
+        pipeline.subtract(
+            input.defense_stage,
+            input.defense,
+            penetration.status_defense,
+        );
+        pipeline.subtract(
+            DamageStageKind::FixedDefense,
+            input.fixed_defense,
+            penetration.fixed_defense,
+        );
+        pipeline.subtract(
+            DamageStageKind::FixedReduction,
+            input.fixed_reduction,
+            penetration.fixed_reduction,
+        );
+
+        // カット率・属性ボーナス・区分ごとのダメージ増加・レベル差補正
+        pipeline.multiply(
+            DamageStageKind::CutRate,
+            1.0 - penetration.apply_cut_rate(input.cut_rate),
+        );
+        pipeline.set_penetration(penetration.cut_rate);
+        pipeline.multiply(DamageStageKind::ElementBonus, 1.0 + input.element_bonus);
+        for category in DamageCategory::ALL {
+            pipeline.multiply(
+                DamageStageKind::DamageCategory(category),
+                input.modifiers.multiplier(category),
+            );
+        }
+        pipeline.multiply(DamageStageKind::LevelCorrection, input.level_multiplier);
+    }
+}
+
+/// バージョン名から計算式を取得
+pub fn formula_by_version(version: &str) -> Option<Box<dyn DamageFormula>> {
+    match version {
+        CURRENT_FORMULA => Some(Box::new(CurrentFormula)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::character::Character;
+    use crate::models::damage::DamageCalculator;
+    use crate::models::element::Element;
+    use crate::models::equipment::{Equipment, EquipmentSet};
+    use crate::models::monster::Monster;
+    use crate::models::skill::{DamageType, Skill};
+
+    /// バージョン切り替えのテスト用の架空の計算式
+    ///
+    /// ダメージ増加は区分を問わずすべて加算し、レベル差補正はない。
+    #[derive(Debug, Clone, Copy, Default)]
+    struct AdditiveFormula;
+
+    impl DamageFormula for AdditiveFormula {
+        fn version(&self) -> &'static str {
+            "additive"
+        }
+
+        fn apply(&self, input: &FormulaInput, pipeline: &mut DamagePipeline) {
+            let penetration = &input.penetration;
+
+            pipeline.multiply(DamageStageKind::SkillMultiplier, input.skill_multiplier);
+            pipeline.truncate();
+
+            pipeline.subtract(
+                input.defense_stage,
+                input.defense,
+                penetration.status_defense,
+            );
+            pipeline.subtract(
+                DamageStageKind::FixedDefense,
+                input.fixed_defense,
+                penetration.fixed_defense,
+            );
+            pipeline.subtract(
+                DamageStageKind::FixedReduction,
+                input.fixed_reduction,
+                penetration.fixed_reduction,
+            );
+
+            pipeline.multiply(
+                DamageStageKind::CutRate,
+                1.0 - penetration.apply_cut_rate(input.cut_rate),
+            );
+            pipeline.set_penetration(penetration.cut_rate);
+            pipeline.multiply(DamageStageKind::ElementBonus, 1.0 + input.element_bonus);
+            pipeline.multiply(
+                DamageStageKind::DamageCategory(DamageCategory::DamageIncrease),
+                (1.0 + input.modifiers.total_percent() / 100.0).max(0.0),
+            );
+        }
+    }
+
+    #[test]
+    fn test_versions_compare_before_and_after() {
+        let equipment_set = EquipmentSet {
+            weapon: Some(
+                Equipment::test_weapon(1100, Element::None)
+                    .with_test_option("与ダメージ+20%", 20.0)
+                    .with_test_option("スキルダメージ+30%", 30.0),
+            ),
+            ..EquipmentSet::new()
+        };
+        let monster = Monster::new("dummy", "ダミー", 1, 100000, 100, 0, 0, 0.0, 0);
+        let skill = Skill::new("slash", "スラッシュ", 1.0, 0.0, 1, DamageType::Physical);
+        let character = Character::new();
+
+        let mut current = DamageCalculator::new();
+        let mut additive = DamageCalculator::new().with_formula(Box::new(AdditiveFormula));
+
+        // 現行: 1000 × 1.2 × 1.3 = 1560、すべて加算: 1000 × (1 + 0.2 + 0.3) = 1500
+        let after = current.calculate_damage(&character, &equipment_set, &skill, &monster);
+        let before = additive.calculate_damage(&character, &equipment_set, &skill, &monster);
+        assert_eq!(after.base_damage, 1560);
+        assert_eq!(after.formula_version, CURRENT_FORMULA);
+        assert_eq!(before.base_damage, 1500);
+        assert_eq!(before.formula_version, "additive");
+    }
+
+    #[test]
+    fn test_unknown_version() {
+        assert!(formula_by_version("v0").is_none());
+        for version in FORMULA_VERSIONS {
+            assert_eq!(formula_by_version(version).unwrap().version(), version);
+        }
+    }
+}
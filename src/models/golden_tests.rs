@@ -0,0 +1,145 @@
+//! 記録済みのダメージ（ゴールデンベクター）との照合テスト
+//!
+//! 各ケースについて、`DamageCalculator` の最小・最大ダメージ（とクリティカルダメージ）が
+//! 許容誤差内で一致することを確認する。コーパスは2つに分けている。
+//!
+//! - `testdata/damage_golden.json`: 現行の計算式からの手計算（`"source": "hand-calculated"`）。
+//!   計算式の意図しない変化を検出する回帰スナップショットで、計算式の誤りは検出できない。
+//! - `testdata/damage_measured.json`: ゲーム内の実測値。まだ収録ケースはなく、
+//!   実測値との照合は測定値を追加するまで行われない。
+//!   追加するときは `source` に測定元（測定日・サーバー・スクリーンショットなど）を書き、
+//!   キャラクターのレベルとレベル差補正も測定時のものにする。
+//!
+//! 計算機はJavaScript向けの計算と同じく、指定がなければレベル差補正なしで作成する。
+
+use super::buff::Buff;
+use super::character::Character;
+use super::damage::DamageCalculator;
+use super::equipment::EquipmentSet;
+use super::formula::{formula_by_version, CURRENT_FORMULA};
+use super::level_correction::LevelCorrection;
+use super::monster::Monster;
+use super::skill::Skill;
+use serde::Deserialize;
+
+/// 許容誤差の既定値（期待値に対する割合）
+const DEFAULT_TOLERANCE: f64 = 0.005;
+
+/// 手計算のケースの測定元
+const HAND_CALCULATED: &str = "hand-calculated";
+
+#[derive(Debug, Deserialize)]
+struct GoldenCorpus {
+    cases: Vec<GoldenCase>,
+}
+
+/// 1件分の記録
+#[derive(Debug, Deserialize)]
+struct GoldenCase {
+    name: String,
+    source: String, // 測定元（ゲーム内測定・手計算など）
+    #[serde(default)]
+    formula: Option<String>, // 計算式のバージョン（未指定なら現行）
+    #[serde(default)]
+    character: Option<Character>, // 未指定ならデフォルトキャラクター
+    equipment: EquipmentSet,
+    #[serde(default)]
+    skill: Option<Skill>, // 未指定なら通常攻撃
+    monster: Monster,
+    #[serde(default)]
+    buffs: Vec<Buff>,
+    #[serde(default)]
+    level_correction: LevelCorrection, // 未指定なら補正なし
+    expected: GoldenExpectation,
+    #[serde(default)]
+    tolerance: Option<f64>,
+}
+
+/// 観測されたダメージ
+#[derive(Debug, Deserialize)]
+struct GoldenExpectation {
+    min_damage: u64,
+    max_damage: u64,
+    #[serde(default)]
+    critical_damage: Option<u64>,
+}
+
+fn load_corpus(json: &str, path: &str) -> GoldenCorpus {
+    serde_json::from_str(json)
+        .unwrap_or_else(|error| panic!("{} の形式が正しくありません: {}", path, error))
+}
+
+/// 期待値との差が許容誤差（割合、最低でも端数1）以内か
+fn within_tolerance(actual: u64, expected: u64, tolerance: f64) -> bool {
+    let allowed = (expected as f64 * tolerance).max(1.0);
+    (actual as f64 - expected as f64).abs() <= allowed
+}
+
+/// コーパスの全ケースを計算し、許容誤差を超えたものを返す
+fn check_corpus(corpus: &GoldenCorpus) -> Vec<String> {
+    let mut failures = Vec::new();
+    for case in &corpus.cases {
+        let version = case.formula.as_deref().unwrap_or(CURRENT_FORMULA);
+        let formula = formula_by_version(version)
+            .unwrap_or_else(|| panic!("{}: 不明な計算式のバージョン {}", case.name, version));
+        let mut calculator = DamageCalculator::with_seed(0)
+            .with_formula(formula)
+            .with_buffs(case.buffs.clone())
+            .with_level_correction(case.level_correction.clone());
+
+        let character = case.character.clone().unwrap_or_default();
+        let skill = case.skill.clone().unwrap_or_else(Skill::normal_attack);
+        let result =
+            calculator.calculate_damage(&character, &case.equipment, &skill, &case.monster);
+
+        let tolerance = case.tolerance.unwrap_or(DEFAULT_TOLERANCE);
+        let mut checks = vec![
+            ("min_damage", result.min_damage, case.expected.min_damage),
+            ("max_damage", result.max_damage, case.expected.max_damage),
+        ];
+        if let Some(critical) = case.expected.critical_damage {
+            checks.push(("critical_damage", result.critical_damage, critical));
+        }
+
+        for (field, actual, expected) in checks {
+            if !within_tolerance(actual, expected, tolerance) {
+                failures.push(format!(
+                    "{} ({}): {} = {}, 期待値 {}",
+                    case.name, case.source, field, actual, expected
+                ));
+            }
+        }
+    }
+    failures
+}
+
+#[test]
+fn test_golden_vectors() {
+    let corpus = load_corpus(
+        include_str!("../../testdata/damage_golden.json"),
+        "testdata/damage_golden.json",
+    );
+    assert!(!corpus.cases.is_empty());
+
+    let failures = check_corpus(&corpus);
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
+
+#[test]
+fn test_measured_vectors() {
+    let corpus = load_corpus(
+        include_str!("../../testdata/damage_measured.json"),
+        "testdata/damage_measured.json",
+    );
+    // 実測値のコーパスには測定元のわかるケースだけを入れる
+    for case in &corpus.cases {
+        assert!(
+            !case.source.is_empty() && case.source != HAND_CALCULATED,
+            "{}: 実測値には測定元が必要です",
+            case.name
+        );
+    }
+
+    let failures = check_corpus(&corpus);
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
@@ -0,0 +1,318 @@
+use super::damage::{DamageCalculator, DamageResult};
+use serde::{Deserialize, Serialize};
+
+/// 厳密計算（畳み込み）を行う計算量の上限（HP × 最大ヒット数）
+pub const EXACT_WORK_LIMIT: u64 = 20_000_000;
+/// 厳密計算を行うHPの上限（HP個の確率を持つ配列を3つ使うため、メモリ量を抑える）
+pub const EXACT_HP_LIMIT: u64 = 1_000_000;
+/// シミュレーションで抽選するヒット数の上限
+pub const SIMULATION_HIT_BUDGET: u64 = 10_000_000;
+/// シミュレーションの最大試行回数
+pub const MAX_SIMULATION_TRIALS: u64 = 10_000;
+/// シミュレーションの最小試行回数
+pub const MIN_SIMULATION_TRIALS: u64 = 10;
+/// 確率を求めるヒット数の上限（これを超えるヒット数での撃破は数えない）
+pub const MAX_TRACKED_HITS: u64 = 1_000_000;
+
+/// 撃破確率の計算方法
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProbabilityMethod {
+    Exact,      // 畳み込みによる厳密計算
+    Simulation, // モンテカルロシミュレーション
+}
+
+/// ヒット数ごとの累積撃破確率
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KillProbability {
+    pub method: ProbabilityMethod,
+    #[serde(serialize_with = "crate::utils::serialize_js_number")]
+    pub trials: u64, // シミュレーションの試行回数（厳密計算では0）
+    /// `cumulative[n - 1]` が nヒット以内に倒せる確率
+    pub cumulative: Vec<f64>,
+}
+
+impl KillProbability {
+    /// 1ヒットのダメージ分布からHPを削りきるまでの累積撃破確率を求める
+    ///
+    /// 1ヒットのダメージは通常ダメージ（最小〜最大の一様分布）とクリティカルダメージの混合で、
+    /// 外れた場合は0ダメージ。
+    /// 必中なら毎ヒット最小ダメージ以上を与えるため、HP / 最小ダメージ のヒット数で必ず倒せる。
+    /// 外れがある場合はその回数を命中率で割り増したヒット数までを求める。
+    /// HPと計算量が上限以内なら畳み込みで厳密に、それ以外はシミュレーションで求める。
+    pub fn compute(calculator: &mut DamageCalculator, result: &DamageResult, hp: u64) -> Self {
+        let max_hits = Self::max_hits(result, hp);
+
+        if hp <= EXACT_HP_LIMIT && hp.saturating_mul(max_hits as u64) <= EXACT_WORK_LIMIT {
+            Self::exact(result, hp, max_hits)
+        } else {
+            Self::simulate(calculator, result, hp, max_hits)
+        }
+    }
+
+    /// 必ず（外れがある場合はほぼ確実に）倒せるヒット数
+    ///
+    /// `MAX_TRACKED_HITS` を上限とする。
+    fn max_hits(result: &DamageResult, hp: u64) -> u32 {
+        let min_hit = result.min_damage.min(result.critical_damage).max(1);
+        let hits = hp.div_ceil(min_hit).max(1);
+        let hits = if result.hit_chance >= 1.0 {
+            hits
+        } else {
+            // 必要な命中数に揺らぎの分の余裕を足し、命中率で割り増す
+            let hits = hits as f64;
+            let margin = 4.0 * hits.sqrt() + 12.0;
+            ((hits + margin) / result.hit_chance.max(f32::EPSILON) as f64).ceil() as u64
+        };
+        hits.min(MAX_TRACKED_HITS) as u32
+    }
+
+    /// 畳み込みによる厳密計算
+    fn exact(result: &DamageResult, hp: u64, max_hits: u32) -> Self {
+        let hp = hp as usize;
+        let min = result.min_damage as usize;
+        let max = result.max_damage as usize;
+        let critical = result.critical_damage as usize;
+        let hit_chance = result.hit_chance.clamp(0.0, 1.0) as f64;
+        let miss_chance = 1.0 - hit_chance;
+        let critical_rate = result.critical_rate as f64 * hit_chance;
+        let normal_each = (1.0 - result.critical_rate as f64) * hit_chance / (max - min + 1) as f64;
+
+        // alive[d]: 累計ダメージがdでまだ倒せていない確率
+        // 配列はヒットごとに作り直さず使い回す
+        let mut alive = vec![0.0f64; hp.max(1)];
+        alive[0] = 1.0;
+        let mut next = vec![0.0f64; alive.len()];
+        let mut prefix = vec![0.0f64; alive.len() + 1];
+        let mut killed = 0.0;
+        let mut cumulative = Vec::with_capacity(max_hits as usize);
+
+        for _ in 0..max_hits {
+            // 通常ダメージの一様分布は累積和で畳み込む
+            for (i, p) in alive.iter().enumerate() {
+                prefix[i + 1] = prefix[i] + p;
+            }
+
+            for (d, slot) in next.iter_mut().enumerate() {
+                *slot = if d < min {
+                    0.0
+                } else {
+                    let from = d.saturating_sub(max);
+                    let to = d - min;
+                    (prefix[to + 1] - prefix[from]) * normal_each
+                };
+            }
+            if critical_rate > 0.0 {
+                for d in 0..alive.len().saturating_sub(critical) {
+                    next[d + critical] += alive[d] * critical_rate;
+                }
+            }
+            if miss_chance > 0.0 {
+                for (slot, p) in next.iter_mut().zip(&alive) {
+                    *slot += p * miss_chance;
+                }
+            }
+
+            let alive_before: f64 = alive.iter().sum();
+            let alive_after: f64 = next.iter().sum();
+            killed += (alive_before - alive_after).max(0.0);
+            cumulative.push(killed.min(1.0));
+            std::mem::swap(&mut alive, &mut next);
+
+            if alive_after <= 1e-12 {
+                break;
+            }
+        }
+
+        Self {
+            method: ProbabilityMethod::Exact,
+            trials: 0,
+            cumulative,
+        }
+    }
+
+    /// モンテカルロシミュレーション
+    fn simulate(
+        calculator: &mut DamageCalculator,
+        result: &DamageResult,
+        hp: u64,
+        max_hits: u32,
+    ) -> Self {
+        let trials = (SIMULATION_HIT_BUDGET / max_hits as u64)
+            .clamp(MIN_SIMULATION_TRIALS, MAX_SIMULATION_TRIALS);
+        let mut kills_at = vec![0u64; max_hits as usize];
+
+        for _ in 0..trials {
+            let mut damage: u64 = 0;
+            let mut hits = 0;
+            while damage < hp && hits < max_hits as usize {
+                damage = damage.saturating_add(calculator.roll_hit(result).0);
+                hits += 1;
+            }
+            if damage >= hp {
+                kills_at[hits - 1] += 1;
+            }
+        }
+
+        let mut killed = 0;
+        let cumulative = kills_at
+            .iter()
+            .map(|&count| {
+                killed += count;
+                killed as f64 / trials as f64
+            })
+            .collect();
+
+        Self {
+            method: ProbabilityMethod::Simulation,
+            trials,
+            cumulative,
+        }
+    }
+
+    /// nヒット以内に倒せる確率
+    pub fn within_hits(&self, hits: u32) -> f64 {
+        if hits == 0 {
+            return 0.0;
+        }
+        self.cumulative
+            .get(hits as usize - 1)
+            .or(self.cumulative.last())
+            .copied()
+            .unwrap_or(0.0)
+    }
+
+    /// 指定した秒数以内に倒せる確率
+    ///
+    /// 秒数内に使い終わる回数 × ヒット数 のヒット数で判定する。
+    pub fn within_seconds(&self, seconds: f32, result: &DamageResult) -> f64 {
+        let casts = (seconds / result.cycle_time).floor() as u32;
+        self.within_hits(casts.saturating_mul(result.hit_count.max(1)))
+    }
+
+    /// 指定した確率以上で倒せる最小のヒット数
+    pub fn hits_for_probability(&self, probability: f64) -> Option<u32> {
+        self.cumulative
+            .iter()
+            .position(|&p| p >= probability - 1e-9)
+            .map(|index| index as u32 + 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::skill::DamageType;
+
+    fn damage_result(min: u64, max: u64, critical_rate: f32, critical: u64) -> DamageResult {
+        DamageResult {
+            base_damage: (min + max) / 2,
+            min_damage: min,
+            max_damage: max,
+            average_damage: 0.0,
+            critical_rate,
+            critical_damage: critical,
+            hit_chance: 1.0,
+            element_bonus: 0.0,
+            hits_to_kill: 0,
+            damage_type: DamageType::Physical,
+            attack: 0,
+            hit_count: 1,
+            damage_per_cast: 0.0,
+            casts_to_kill: 0,
+            cycle_time: 1.0,
+            dps: 0.0,
+            time_to_kill: 0.0,
+            time_to_kill_text: String::new(),
+            breakdown: None,
+            formula_version: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_exact_two_valued_distribution() {
+        // 通常 1 か 2（各50%）、HP 3
+        // 2ヒットで倒せるのは 1+2, 2+1, 2+2 の 3/4
+        let result = damage_result(1, 2, 0.0, 1);
+        let mut calculator = DamageCalculator::with_seed(1);
+        let probability = KillProbability::compute(&mut calculator, &result, 3);
+
+        assert_eq!(probability.method, ProbabilityMethod::Exact);
+        assert_eq!(probability.within_hits(1), 0.0);
+        assert!((probability.within_hits(2) - 0.75).abs() < 1e-9);
+        assert!((probability.within_hits(3) - 1.0).abs() < 1e-9);
+        assert_eq!(probability.hits_for_probability(0.95), Some(3));
+        // 1秒に1ヒットなので2.5秒以内は2ヒット以内と同じ
+        assert!((probability.within_seconds(2.5, &result) - 0.75).abs() < 1e-9);
+        // 非常に長い秒数でもヒット数の計算があふれない
+        let multi_hit = DamageResult {
+            hit_count: 3,
+            ..result.clone()
+        };
+        assert!((probability.within_seconds(2e9, &multi_hit) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_exact_with_critical() {
+        // 通常 100 (70%)、クリティカル 150 (30%)、HP 250
+        // 2ヒットで倒せるのは クリティカル+クリティカル と 通常+クリティカル の組み合わせ
+        let result = damage_result(100, 100, 0.3, 150);
+        let mut calculator = DamageCalculator::with_seed(1);
+        let probability = KillProbability::compute(&mut calculator, &result, 250);
+
+        let expected = 0.3 * 0.3 + 2.0 * 0.7 * 0.3;
+        assert!((probability.within_hits(2) - expected).abs() < 1e-6);
+        assert!((probability.within_hits(3) - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_exact_with_misses() {
+        // 100ダメージ、命中率50%、HP 200
+        // 2ヒットで倒せるのは両方当たった場合、3ヒットでは3回中2回以上当たった場合
+        let mut result = damage_result(100, 100, 0.0, 100);
+        result.hit_chance = 0.5;
+        let mut calculator = DamageCalculator::with_seed(1);
+        let probability = KillProbability::compute(&mut calculator, &result, 200);
+
+        assert!((probability.within_hits(2) - 0.25).abs() < 1e-9);
+        assert!((probability.within_hits(3) - 0.5).abs() < 1e-9);
+        assert!(probability.within_hits(probability.cumulative.len() as u32) > 0.999999);
+    }
+
+    #[test]
+    fn test_simulation_matches_exact() {
+        let result = damage_result(900, 1100, 0.2, 1500);
+        let mut calculator = DamageCalculator::with_seed(42);
+        let exact = KillProbability::exact(&result, 10000, 12);
+        let simulated = KillProbability::simulate(&mut calculator, &result, 10000, 12);
+
+        assert_eq!(simulated.method, ProbabilityMethod::Simulation);
+        for hits in 8..=11 {
+            assert!((exact.within_hits(hits) - simulated.within_hits(hits)).abs() < 0.03);
+        }
+    }
+
+    #[test]
+    fn test_large_hp_uses_simulation_even_for_one_hit() {
+        // 1ヒットで倒せても、HPが大きいとHP個の配列を確保しないようにシミュレーションにする
+        let hp = 20_000_000;
+        let result = damage_result(hp, hp, 0.0, hp);
+        let mut calculator = DamageCalculator::with_seed(3);
+        let probability = KillProbability::compute(&mut calculator, &result, hp);
+
+        assert_eq!(probability.method, ProbabilityMethod::Simulation);
+        assert_eq!(probability.within_hits(1), 1.0);
+    }
+
+    #[test]
+    fn test_extreme_hp_uses_simulation() {
+        // HP が u64 の上限でも、1ヒット u64::MAX / 4 なら4〜5ヒットで倒せる
+        let quarter = u64::MAX / 4;
+        let result = damage_result(quarter, quarter, 0.0, quarter);
+        let mut calculator = DamageCalculator::with_seed(7);
+        let probability = KillProbability::compute(&mut calculator, &result, u64::MAX);
+
+        assert_eq!(probability.method, ProbabilityMethod::Simulation);
+        assert_eq!(probability.within_hits(4), 0.0);
+        assert_eq!(probability.within_hits(5), 1.0);
+    }
+}
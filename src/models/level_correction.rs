@@ -0,0 +1,101 @@
+use serde::{Deserialize, Serialize};
+
+/// レベル差の補正曲線の1点
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LevelCorrectionPoint {
+    pub difference: i32, // キャラクターのレベル - モンスターのレベル
+    pub multiplier: f32,
+}
+
+/// レベル差によるダメージ補正
+///
+/// 補正点の間は線形補間し、範囲外は端の倍率をそのまま使う。
+/// 補正点がなければ補正しない。
+/// JavaScriptから渡された補正点も、作成時と同じくレベル差の昇順に並べ替える。
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(from = "UnsortedLevelCorrection")]
+pub struct LevelCorrection {
+    pub points: Vec<LevelCorrectionPoint>, // レベル差の昇順
+}
+
+impl LevelCorrection {
+    /// 補正点からレベル差補正を作成
+    pub fn new(points: &[(i32, f32)]) -> Self {
+        let mut points: Vec<LevelCorrectionPoint> = points
+            .iter()
+            .map(|&(difference, multiplier)| LevelCorrectionPoint {
+                difference,
+                multiplier,
+            })
+            .collect();
+        points.sort_by_key(|point| point.difference);
+        Self { points }
+    }
+
+    /// レベル差に対する倍率
+    pub fn multiplier(&self, character_level: u32, monster_level: u32) -> f32 {
+        let difference = character_level as i64 - monster_level as i64;
+        let (Some(first), Some(last)) = (self.points.first(), self.points.last()) else {
+            return 1.0;
+        };
+
+        if difference <= first.difference as i64 {
+            return first.multiplier;
+        }
+        if difference >= last.difference as i64 {
+            return last.multiplier;
+        }
+
+        let upper = self
+            .points
+            .iter()
+            .position(|point| point.difference as i64 >= difference)
+            .unwrap_or(self.points.len() - 1);
+        let (a, b) = (self.points[upper - 1], self.points[upper]);
+        let t = (difference - a.difference as i64) as f32 / (b.difference - a.difference) as f32;
+        a.multiplier + (b.multiplier - a.multiplier) * t
+    }
+}
+
+/// デシリアライズ直後の並べ替え前の補正点
+#[derive(Deserialize)]
+struct UnsortedLevelCorrection {
+    points: Vec<LevelCorrectionPoint>,
+}
+
+impl From<UnsortedLevelCorrection> for LevelCorrection {
+    fn from(unsorted: UnsortedLevelCorrection) -> Self {
+        let mut points = unsorted.points;
+        points.sort_by_key(|point| point.difference);
+        Self { points }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interpolates_and_clamps() {
+        let curve = LevelCorrection::new(&[(10, 1.1), (-20, 0.5), (0, 1.0), (-10, 0.8)]);
+
+        assert_eq!(curve.multiplier(40, 40), 1.0);
+        // -15 は -20 (0.5) と -10 (0.8) の中間
+        assert!((curve.multiplier(25, 40) - 0.65).abs() < 1e-6);
+        // 範囲外は端の値
+        assert_eq!(curve.multiplier(1, 40), 0.5);
+        assert_eq!(curve.multiplier(99, 40), 1.1);
+        // 補正点がなければ補正しない
+        assert_eq!(LevelCorrection::default().multiplier(1, 99), 1.0);
+    }
+
+    #[test]
+    fn test_deserialize_sorts_points() {
+        let json =
+            r#"{"points":[{"difference":0,"multiplier":1.0},{"difference":-10,"multiplier":0.5}]}"#;
+        let curve: LevelCorrection = serde_json::from_str(json).unwrap();
+
+        assert_eq!(curve, LevelCorrection::new(&[(-10, 0.5), (0, 1.0)]));
+        assert!((curve.multiplier(35, 40) - 0.75).abs() < 1e-6);
+    }
+}
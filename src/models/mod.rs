@@ -1,3 +1,20 @@
+pub mod buff;
+pub mod character;
 pub mod damage;
+pub mod damage_category;
+pub mod element;
 pub mod equipment;
+pub mod formula;
+#[cfg(test)]
+mod golden_tests;
+pub mod kill_probability;
+pub mod level_correction;
 pub mod monster;
+pub mod optimizer;
+pub mod option_effect;
+pub mod penetration;
+pub mod rotation;
+pub mod sensitivity;
+pub mod simulation;
+pub mod skill;
+pub mod solver;
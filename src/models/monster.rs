@@ -1,6 +1,8 @@
+use super::element::Element;
 use serde::{Deserialize, Serialize};
-use serde_wasm_bindgen::to_value;
-use wasm_bindgen::prelude::*;
+
+/// 最低命中率（回避がどれだけ高くてもこの確率では当たる）
+pub const MIN_HIT_CHANCE: f32 = 0.05;
 
 /// モンスターの情報を簡略化
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -8,22 +10,30 @@ pub struct Monster {
     pub id: String,
     pub name: String,
     pub level: u32,
-    pub hp: u32,
+    #[serde(serialize_with = "crate::utils::serialize_js_number")]
+    pub hp: u64,
     pub defense: u32,            // ステータス防御
     pub fixed_defense: u32,      // 固定防御
     pub fixed_reduction: u32,    // 固定減少
     pub cut_rate: f32,           // カット率 (0.0 ~ 1.0)
     pub element_resistance: u32, // 属性耐性値
+    #[serde(default)]
+    pub element: Element, // 属性の種類
+    #[serde(default)]
+    pub magic_resist: Option<u32>, // 魔法防御（Noneならステータス防御と同じ）
+    #[serde(default)]
+    pub evasion: u32, // 回避
     pub image_url: Option<String>,
 }
 
 impl Monster {
     /// 新しいモンスターを作成
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         id: impl Into<String>,
         name: impl Into<String>,
         level: u32,
-        hp: u32,
+        hp: u64,
         defense: u32,
         fixed_defense: u32,
         fixed_reduction: u32,
@@ -40,10 +50,47 @@ impl Monster {
             fixed_reduction,
             cut_rate,
             element_resistance,
+            element: Element::None,
+            magic_resist: None,
+            evasion: 0,
             image_url: None,
         }
     }
 
+    /// 属性を設定
+    pub fn with_element(mut self, element: Element) -> Self {
+        self.element = element;
+        self
+    }
+
+    /// 魔法防御を設定
+    pub fn with_magic_resist(mut self, magic_resist: u32) -> Self {
+        self.magic_resist = Some(magic_resist);
+        self
+    }
+
+    /// 魔法攻撃に対する防御値
+    pub fn effective_magic_resist(&self) -> u32 {
+        self.magic_resist.unwrap_or(self.defense)
+    }
+
+    /// 回避を設定
+    pub fn with_evasion(mut self, evasion: u32) -> Self {
+        self.evasion = evasion;
+        self
+    }
+
+    /// 命中に対する命中率
+    ///
+    /// 命中が回避以上なら必中、それ以外は 命中 / 回避（最低 `MIN_HIT_CHANCE`）。
+    pub fn hit_chance(&self, accuracy: u32) -> f32 {
+        if accuracy >= self.evasion {
+            1.0
+        } else {
+            (accuracy as f32 / self.evasion as f32).max(MIN_HIT_CHANCE)
+        }
+    }
+
     /// 画像URLを設定
     pub fn with_image(mut self, image_url: impl Into<String>) -> Self {
         self.image_url = Some(image_url.into());
@@ -52,7 +99,7 @@ impl Monster {
 }
 
 /// モンスターのデータベース
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct MonsterDatabase {
     pub monsters: Vec<Monster>,
 }
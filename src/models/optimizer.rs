@@ -0,0 +1,551 @@
+use super::character::Character;
+use super::damage::{DamageCalculator, DamageResult};
+use super::element::Element;
+use super::equipment::{Equipment, EquipmentOption, EquipmentSet, EquipmentType};
+use super::monster::Monster;
+use super::option_effect::OptionEffect;
+use super::skill::Skill;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+/// 既定で返す装備セットの数
+pub const DEFAULT_TOP_N: usize = 5;
+
+/// 最適化の目的
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OptimizeGoal {
+    #[default]
+    MaxDamage, // 平均ダメージ（命中・クリティカル込みの期待値）を最大化
+    MinTimeToKill, // 撃破時間を最小化
+}
+
+impl OptimizeGoal {
+    /// 計算結果の評価値（ダメージまたは撃破時間）
+    fn value(self, result: &DamageResult) -> f64 {
+        match self {
+            OptimizeGoal::MaxDamage => result.average_damage,
+            OptimizeGoal::MinTimeToKill => result.time_to_kill as f64,
+        }
+    }
+
+    /// 評価値を大きいほど良い値に変換
+    fn utility(self, score: f64) -> f64 {
+        match self {
+            OptimizeGoal::MaxDamage => score,
+            OptimizeGoal::MinTimeToKill => -score,
+        }
+    }
+}
+
+/// 重みつきの対象モンスター
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeightedMonster {
+    pub monster: Monster,
+    pub weight: f64, // 評価値の加重平均での重み
+}
+
+impl WeightedMonster {
+    /// 新しい対象モンスターを作成
+    pub fn new(monster: Monster, weight: f64) -> Self {
+        Self { monster, weight }
+    }
+}
+
+/// 最適化で見つかった装備セット
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OptimizedSet {
+    pub equipment: EquipmentSet,
+    pub score: f64, // 対象モンスターでの評価値の加重平均（平均ダメージまたは撃破時間）
+    pub results: Vec<DamageResult>, // 対象モンスターごとの計算結果
+    #[serde(skip)]
+    utility: f64,
+}
+
+/// 最適化の結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OptimizationResult {
+    pub sets: Vec<OptimizedSet>, // 評価の良い順
+    #[serde(serialize_with = "crate::utils::serialize_js_number")]
+    pub evaluated_sets: u64, // 最後まで計算した装備セットの数
+    #[serde(serialize_with = "crate::utils::serialize_js_number")]
+    pub pruned_branches: u64, // 上限評価で打ち切った分岐の数
+}
+
+/// 同じ種類の装備を割り当てるスロットのまとまり
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SlotGroup {
+    Weapon,
+    Armor,
+    Accessories, // アクセサリー2枠（同じアイテムは2回使えない）
+    Special,
+}
+
+impl SlotGroup {
+    /// 探索する順番（武器で攻撃属性が決まるので最初に決める）
+    const ORDER: [SlotGroup; 4] = [
+        SlotGroup::Weapon,
+        SlotGroup::Armor,
+        SlotGroup::Accessories,
+        SlotGroup::Special,
+    ];
+
+    fn equipment_type(self) -> EquipmentType {
+        match self {
+            SlotGroup::Weapon => EquipmentType::Weapon,
+            SlotGroup::Armor => EquipmentType::Armor,
+            SlotGroup::Accessories => EquipmentType::Accessory,
+            SlotGroup::Special => EquipmentType::Special,
+        }
+    }
+
+    fn slot_count(self) -> usize {
+        match self {
+            SlotGroup::Accessories => 2,
+            _ => 1,
+        }
+    }
+
+    /// スロットにアイテムを割り当てる（足りない枠は空ける）
+    fn assign(self, set: &mut EquipmentSet, items: &[&Equipment]) {
+        let mut items = items.iter().map(|&item| item.clone());
+        match self {
+            SlotGroup::Weapon => set.weapon = items.next(),
+            SlotGroup::Armor => set.armor = items.next(),
+            SlotGroup::Accessories => {
+                set.accessory1 = items.next();
+                set.accessory2 = items.next();
+            }
+            SlotGroup::Special => set.special = items.next(),
+        }
+    }
+}
+
+/// スロットのまとまりごとの候補
+struct GroupCandidates<'a> {
+    group: SlotGroup,
+    choices: Vec<Vec<&'a Equipment>>, // 空きを含む割り当て方
+    optimistic: Equipment,            // どの割り当てよりもダメージが出る架空の装備
+}
+
+impl<'a> GroupCandidates<'a> {
+    fn new(group: SlotGroup, inventory: &'a [Equipment]) -> Self {
+        let items: Vec<&Equipment> = inventory
+            .iter()
+            .filter(|item| item.equipment_type == group.equipment_type())
+            .collect();
+
+        let mut choices = vec![vec![]];
+        for (i, &item) in items.iter().enumerate() {
+            choices.push(vec![item]);
+            if group.slot_count() == 2 {
+                for &other in &items[i + 1..] {
+                    choices.push(vec![item, other]);
+                }
+            }
+        }
+
+        Self {
+            group,
+            choices,
+            optimistic: optimistic_equipment(&items, group.slot_count()),
+        }
+    }
+}
+
+/// 上限評価用の架空の装備
+///
+/// 攻撃力と属性値は上位 `slots` 個の合計、オプションは解析後の効果量がプラスのものと
+/// 「〇〇無効」（効果量0で保存される）をすべて持つ。
+/// 属性値は無属性にして、どの攻撃属性にも加算されるようにする。
+fn optimistic_equipment(items: &[&Equipment], slots: usize) -> Equipment {
+    let top_sum = |mut values: Vec<u32>| {
+        values.sort_unstable_by(|a, b| b.cmp(a));
+        values
+            .into_iter()
+            .take(slots)
+            .fold(0u32, u32::saturating_add)
+    };
+
+    Equipment {
+        name: "上限評価".to_string(),
+        equipment_type: EquipmentType::Special,
+        attack: top_sum(items.iter().map(|item| item.attack).collect()),
+        defense: 0,
+        element_value: top_sum(items.iter().map(|item| item.element_value).collect()),
+        element: Element::None,
+        options: items
+            .iter()
+            .flat_map(|item| item.options.iter())
+            .filter(|option| match option.effect() {
+                OptionEffect::BypassStage(_) => true,
+                effect => effect.amount().is_some_and(|amount| amount > 0.0),
+            })
+            .cloned()
+            .collect::<Vec<EquipmentOption>>(),
+    }
+}
+
+/// 所持装備から最適な装備セットを探す
+///
+/// スロットのまとまりごとに割り当てを分岐し、残りのスロットに
+/// 「どの候補よりも強い架空の装備」を入れた評価を上限として、
+/// 上位N件に入れない分岐を打ち切る（分枝限定法）。
+/// 上限評価は、攻撃力・属性値・プラスのオプション・「〇〇無効」が増えてもダメージが下がらないことを前提とする。
+#[derive(Debug, Clone)]
+pub struct EquipmentOptimizer {
+    pub character: Character,
+    pub skill: Skill,
+    pub goal: OptimizeGoal,
+    pub top_n: usize,
+}
+
+impl EquipmentOptimizer {
+    /// 新しい最適化を作成（平均ダメージの最大化、上位5件）
+    pub fn new(character: Character, skill: Skill) -> Self {
+        Self {
+            character,
+            skill,
+            goal: OptimizeGoal::default(),
+            top_n: DEFAULT_TOP_N,
+        }
+    }
+
+    /// 最適化の目的を設定
+    pub fn with_goal(mut self, goal: OptimizeGoal) -> Self {
+        self.goal = goal;
+        self
+    }
+
+    /// 返す装備セットの数を設定
+    pub fn with_top_n(mut self, top_n: usize) -> Self {
+        self.top_n = top_n;
+        self
+    }
+
+    /// 1体のモンスターに対して最適化
+    pub fn optimize(
+        &self,
+        calculator: &mut DamageCalculator,
+        inventory: &[Equipment],
+        monster: &Monster,
+    ) -> OptimizationResult {
+        self.optimize_weighted(
+            calculator,
+            inventory,
+            &[WeightedMonster::new(monster.clone(), 1.0)],
+        )
+    }
+
+    /// 重みつきの複数モンスターに対して最適化
+    pub fn optimize_weighted(
+        &self,
+        calculator: &mut DamageCalculator,
+        inventory: &[Equipment],
+        targets: &[WeightedMonster],
+    ) -> OptimizationResult {
+        let mut search = Search {
+            optimizer: self,
+            calculator,
+            targets,
+            groups: SlotGroup::ORDER
+                .iter()
+                .map(|&group| GroupCandidates::new(group, inventory))
+                .collect(),
+            result: OptimizationResult {
+                sets: Vec::new(),
+                evaluated_sets: 0,
+                pruned_branches: 0,
+            },
+        };
+
+        if self.top_n > 0 && !targets.is_empty() {
+            search.branch(0, &mut EquipmentSet::new());
+        }
+        search.result
+    }
+}
+
+/// 分枝限定法の探索状態
+struct Search<'a, 'b> {
+    optimizer: &'a EquipmentOptimizer,
+    calculator: &'a mut DamageCalculator,
+    targets: &'a [WeightedMonster],
+    groups: Vec<GroupCandidates<'b>>,
+    result: OptimizationResult,
+}
+
+impl Search<'_, '_> {
+    /// `index` 番目のまとまりの割り当てを分岐
+    fn branch(&mut self, index: usize, set: &mut EquipmentSet) {
+        if index == self.groups.len() {
+            self.record(set);
+            return;
+        }
+
+        // 上限評価の高い割り当てから調べると、早く良い解が見つかって打ち切りが増える
+        let group = self.groups[index].group;
+        let mut children: Vec<(f64, usize)> = (0..self.groups[index].choices.len())
+            .map(|choice| {
+                group.assign(set, &self.groups[index].choices[choice]);
+                (self.upper_bound(index + 1, set), choice)
+            })
+            .collect();
+        children.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(Ordering::Equal));
+
+        for (bound, choice) in children {
+            if self.cannot_enter_top(bound) {
+                self.result.pruned_branches += 1;
+                continue;
+            }
+            group.assign(set, &self.groups[index].choices[choice]);
+            self.branch(index + 1, set);
+        }
+        group.assign(set, &[]);
+    }
+
+    /// `index` 番目以降のスロットに架空の装備を入れた評価（これより良くはならない）
+    fn upper_bound(&mut self, index: usize, set: &EquipmentSet) -> f64 {
+        if index == self.groups.len() {
+            return self.evaluate(set).1;
+        }
+
+        let mut optimistic = optimistic_equipment(&[], 0);
+        for candidates in &self.groups[index..] {
+            optimistic.attack = optimistic
+                .attack
+                .saturating_add(candidates.optimistic.attack);
+            optimistic.element_value = optimistic
+                .element_value
+                .saturating_add(candidates.optimistic.element_value);
+            optimistic
+                .options
+                .extend(candidates.optimistic.options.iter().cloned());
+        }
+
+        // 特殊装備は最後に決めるので、ここではまだ空いている
+        let mut bounded = set.clone();
+        bounded.special = Some(optimistic);
+        self.evaluate(&bounded).1
+    }
+
+    /// 上位N件がそろっていて、その最下位を上回れないか
+    fn cannot_enter_top(&self, bound: f64) -> bool {
+        self.result.sets.len() >= self.optimizer.top_n
+            && self
+                .result
+                .sets
+                .last()
+                .is_some_and(|worst| bound <= worst.utility)
+    }
+
+    /// 対象モンスターごとに計算し、評価値の加重平均と大きいほど良い値を返す
+    fn evaluate(&mut self, set: &EquipmentSet) -> (Vec<DamageResult>, f64, f64) {
+        let optimizer = self.optimizer;
+        let results: Vec<DamageResult> = self
+            .targets
+            .iter()
+            .map(|target| {
+                self.calculator.calculate_damage(
+                    &optimizer.character,
+                    set,
+                    &optimizer.skill,
+                    &target.monster,
+                )
+            })
+            .collect();
+
+        let total_weight: f64 = self.targets.iter().map(|target| target.weight).sum();
+        let weighted: f64 = self
+            .targets
+            .iter()
+            .zip(&results)
+            .map(|(target, result)| target.weight * optimizer.goal.value(result))
+            .sum();
+        let score = if total_weight > 0.0 {
+            weighted / total_weight
+        } else {
+            0.0
+        };
+        (results, score, optimizer.goal.utility(score))
+    }
+
+    /// 完成した装備セットを上位N件に記録
+    fn record(&mut self, set: &EquipmentSet) {
+        self.result.evaluated_sets += 1;
+        let (results, score, utility) = self.evaluate(set);
+        if self.cannot_enter_top(utility) {
+            return;
+        }
+
+        let sets = &mut self.result.sets;
+        let position = sets
+            .iter()
+            .position(|other| utility > other.utility)
+            .unwrap_or(sets.len());
+        sets.insert(
+            position,
+            OptimizedSet {
+                equipment: set.clone(),
+                score,
+                results,
+                utility,
+            },
+        );
+        sets.truncate(self.optimizer.top_n);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inventory() -> Vec<Equipment> {
+        let mut inventory = vec![
+            Equipment::test_item("剣", EquipmentType::Weapon, 1000),
+            Equipment::test_item("大剣", EquipmentType::Weapon, 1200),
+            Equipment::test_item("鎧", EquipmentType::Armor, 0)
+                .with_test_option("与ダメージ+10%", 10.0),
+            Equipment::test_item("紋章", EquipmentType::Special, 50),
+        ];
+        for i in 0..8u32 {
+            inventory.push(
+                Equipment::test_item(&format!("指輪{}", i), EquipmentType::Accessory, i * 20)
+                    .with_test_option("固定防御無視+5%", (i % 3) as f32 * 5.0),
+            );
+        }
+        inventory
+    }
+
+    /// すべての組み合わせを計算した評価値（良い順）
+    fn brute_force(
+        optimizer: &EquipmentOptimizer,
+        inventory: &[Equipment],
+        monster: &Monster,
+    ) -> Vec<f64> {
+        let by_type = |equipment_type| -> Vec<Option<&Equipment>> {
+            std::iter::once(None)
+                .chain(
+                    inventory
+                        .iter()
+                        .filter(|item| item.equipment_type == equipment_type)
+                        .map(Some),
+                )
+                .collect()
+        };
+        let accessories: Vec<&Equipment> = by_type(EquipmentType::Accessory)
+            .into_iter()
+            .flatten()
+            .collect();
+        let mut pairs = vec![(None, None)];
+        for (i, &a) in accessories.iter().enumerate() {
+            pairs.push((Some(a), None));
+            for &b in &accessories[i + 1..] {
+                pairs.push((Some(a), Some(b)));
+            }
+        }
+
+        let mut calculator = DamageCalculator::with_seed(1);
+        let mut scores = Vec::new();
+        for weapon in by_type(EquipmentType::Weapon) {
+            for armor in by_type(EquipmentType::Armor) {
+                for &(accessory1, accessory2) in &pairs {
+                    for special in by_type(EquipmentType::Special) {
+                        let set = EquipmentSet {
+                            weapon: weapon.cloned(),
+                            armor: armor.cloned(),
+                            accessory1: accessory1.cloned(),
+                            accessory2: accessory2.cloned(),
+                            special: special.cloned(),
+                        };
+                        let result = calculator.calculate_damage(
+                            &optimizer.character,
+                            &set,
+                            &optimizer.skill,
+                            monster,
+                        );
+                        scores.push(optimizer.goal.value(&result));
+                    }
+                }
+            }
+        }
+        scores.sort_by(|a, b| b.partial_cmp(a).unwrap());
+        scores
+    }
+
+    #[test]
+    fn test_matches_brute_force_and_prunes() {
+        let monster = Monster::new("boss", "ボス", 40, 1000000, 0, 1500, 0, 0.0, 0);
+        let inventory = inventory();
+        let optimizer =
+            EquipmentOptimizer::new(Character::new(), Skill::normal_attack()).with_top_n(3);
+        let mut calculator = DamageCalculator::with_seed(1);
+
+        let result = optimizer.optimize(&mut calculator, &inventory, &monster);
+        let expected = brute_force(&optimizer, &inventory, &monster);
+
+        let scores: Vec<f64> = result.sets.iter().map(|set| set.score).collect();
+        assert_eq!(scores, expected[..3]);
+        assert!(result.pruned_branches > 0);
+        assert!(result.evaluated_sets < expected.len() as u64);
+
+        // 最良のセットは大剣・鎧・紋章と、固定防御無視の大きい指輪2つ
+        let best = &result.sets[0].equipment;
+        assert_eq!(best.weapon.as_ref().unwrap().name, "大剣");
+        assert!(best.armor.is_some() && best.special.is_some());
+        assert!(best.accessory1.is_some() && best.accessory2.is_some());
+    }
+
+    #[test]
+    fn test_bypass_option_in_later_slot_is_not_pruned() {
+        // 「カット率無効」は効果量0で保存されるが、上限評価から落とすと正しい分岐を打ち切ってしまう
+        let monster = Monster::new("boss", "ボス", 40, 1000000, 0, 0, 0, 0.9, 0);
+        let inventory = vec![
+            Equipment::test_item("剣A", EquipmentType::Weapon, 1000),
+            Equipment::test_item("剣B", EquipmentType::Weapon, 1100),
+            Equipment::test_item("無効の紋章", EquipmentType::Special, 0)
+                .with_test_option("カット率無効", 0.0),
+        ];
+        let optimizer =
+            EquipmentOptimizer::new(Character::new(), Skill::normal_attack()).with_top_n(2);
+        let mut calculator = DamageCalculator::with_seed(1);
+
+        let result = optimizer.optimize(&mut calculator, &inventory, &monster);
+        let expected = brute_force(&optimizer, &inventory, &monster);
+
+        let scores: Vec<f64> = result.sets.iter().map(|set| set.score).collect();
+        assert_eq!(scores, expected[..2]);
+        for set in &result.sets {
+            assert_eq!(set.equipment.special.as_ref().unwrap().name, "無効の紋章");
+        }
+    }
+
+    #[test]
+    fn test_weighted_monsters_and_time_to_kill() {
+        let inventory = vec![
+            Equipment::test_item("剣", EquipmentType::Weapon, 3000),
+            Equipment::test_item("破甲剣", EquipmentType::Weapon, 2000)
+                .with_test_option("固定防御無視+60%", 60.0),
+        ];
+        // 固定防御のない雑魚には攻撃力、固定防御の高いボスには防御無視が効く
+        let mob = Monster::new("mob", "雑魚", 10, 100000, 0, 0, 0, 0.0, 0);
+        let boss = Monster::new("boss", "ボス", 40, 100000, 0, 2500, 0, 0.0, 0);
+        let optimizer = EquipmentOptimizer::new(Character::new(), Skill::normal_attack())
+            .with_goal(OptimizeGoal::MinTimeToKill)
+            .with_top_n(1);
+        let mut calculator = DamageCalculator::with_seed(1);
+
+        let weapon_for = |calculator: &mut DamageCalculator, mob_weight, boss_weight| {
+            let targets = [
+                WeightedMonster::new(mob.clone(), mob_weight),
+                WeightedMonster::new(boss.clone(), boss_weight),
+            ];
+            let result = optimizer.optimize_weighted(calculator, &inventory, &targets);
+            let best = &result.sets[0];
+            assert_eq!(best.results.len(), 2);
+            best.equipment.weapon.as_ref().unwrap().name.clone()
+        };
+
+        assert_eq!(weapon_for(&mut calculator, 1.0, 0.0), "剣");
+        assert_eq!(weapon_for(&mut calculator, 1.0, 1.0), "破甲剣");
+    }
+}
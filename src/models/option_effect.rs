@@ -0,0 +1,306 @@
+use super::damage::DamageStageKind;
+use super::element::Element;
+use super::penetration::Penetration;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// ダメージ増加の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DamageIncreaseKind {
+    All,      // 与ダメージ
+    Physical, // 物理ダメージ
+    Magic,    // 魔法ダメージ
+    Skill,    // スキルダメージ
+}
+
+/// 装備オプションの効果
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum OptionEffect {
+    AttackFlat(f32),                         // 攻撃力+N
+    AttackPercent(f32),                      // 攻撃力+N%
+    DefenseFlat(f32),                        // 防御力+N
+    DefensePercent(f32),                     // 防御力+N%
+    Hp(f32),                                 // HP+N
+    CriticalRate(f32),                       // クリティカル率+N%
+    CriticalDamage(f32),                     // クリティカルダメージ+N%
+    ElementAttack(Element, f32),             // X属性攻撃+N%
+    AllElementAttack(f32),                   // 全属性攻撃+N%
+    ElementResist(Element, f32),             // X属性耐性+N%
+    AllElementResist(f32),                   // 全属性耐性+N%
+    DefenseIgnore(f32),                      // 防御無視+N%
+    FixedDefenseIgnore(f32),                 // 固定防御無視+N%
+    FixedReductionIgnore(f32),               // 固定減少無視+N%
+    CutRatePierce(f32),                      // カット率貫通+N%
+    DamageIncrease(DamageIncreaseKind, f32), // 〇〇ダメージ+N%
+    BypassStage(DamageStageKind),            // 〇〇無効（計算段階を飛ばす）
+    Unknown(String, f32),                    // 解釈できないオプション
+}
+
+impl OptionEffect {
+    /// 「攻撃力+10%」形式のオプション文字列を解析
+    ///
+    /// 文字列中の数値を優先し、数値が読み取れない場合は`value`を使う。
+    pub fn parse(text: &str, value: f32) -> Self {
+        let text = text.trim();
+        let sign_index = text.find(['+', '-', '＋', '－']);
+        let (label, amount) = match sign_index {
+            Some(index) => text.split_at(index),
+            None => (text, ""),
+        };
+        let label = label.trim();
+        let is_percent = amount.ends_with('%') || amount.ends_with('％');
+        let value = Self::parse_amount(amount).unwrap_or(value);
+
+        match label {
+            "攻撃力" if is_percent => OptionEffect::AttackPercent(value),
+            "攻撃力" => OptionEffect::AttackFlat(value),
+            "防御力" if is_percent => OptionEffect::DefensePercent(value),
+            "防御力" => OptionEffect::DefenseFlat(value),
+            "HP" => OptionEffect::Hp(value),
+            "クリティカル率" => OptionEffect::CriticalRate(value),
+            "クリティカルダメージ" => OptionEffect::CriticalDamage(value),
+            "全属性攻撃" => OptionEffect::AllElementAttack(value),
+            "全属性耐性" => OptionEffect::AllElementResist(value),
+            "防御無視" => OptionEffect::DefenseIgnore(value),
+            "固定防御無視" => OptionEffect::FixedDefenseIgnore(value),
+            "固定減少無視" => OptionEffect::FixedReductionIgnore(value),
+            "カット率貫通" => OptionEffect::CutRatePierce(value),
+            "与ダメージ" | "ダメージ" => {
+                OptionEffect::DamageIncrease(DamageIncreaseKind::All, value)
+            }
+            "物理ダメージ" => {
+                OptionEffect::DamageIncrease(DamageIncreaseKind::Physical, value)
+            }
+            "魔法ダメージ" => OptionEffect::DamageIncrease(DamageIncreaseKind::Magic, value),
+            "スキルダメージ" => {
+                OptionEffect::DamageIncrease(DamageIncreaseKind::Skill, value)
+            }
+            _ => {
+                if let Some(element) = label.strip_suffix("属性攻撃").and_then(Self::parse_element)
+                {
+                    OptionEffect::ElementAttack(element, value)
+                } else if let Some(element) =
+                    label.strip_suffix("属性耐性").and_then(Self::parse_element)
+                {
+                    OptionEffect::ElementResist(element, value)
+                } else if let Some(stage) = label
+                    .strip_suffix("無効")
+                    .and_then(DamageStageKind::from_label)
+                {
+                    OptionEffect::BypassStage(stage)
+                } else {
+                    OptionEffect::Unknown(text.to_string(), value)
+                }
+            }
+        }
+    }
+
+    /// 効果量（「〇〇無効」のように数値を持たない効果はNone）
+    pub fn amount(&self) -> Option<f32> {
+        match self {
+            OptionEffect::AttackFlat(v)
+            | OptionEffect::AttackPercent(v)
+            | OptionEffect::DefenseFlat(v)
+            | OptionEffect::DefensePercent(v)
+            | OptionEffect::Hp(v)
+            | OptionEffect::CriticalRate(v)
+            | OptionEffect::CriticalDamage(v)
+            | OptionEffect::ElementAttack(_, v)
+            | OptionEffect::AllElementAttack(v)
+            | OptionEffect::ElementResist(_, v)
+            | OptionEffect::AllElementResist(v)
+            | OptionEffect::DefenseIgnore(v)
+            | OptionEffect::FixedDefenseIgnore(v)
+            | OptionEffect::FixedReductionIgnore(v)
+            | OptionEffect::CutRatePierce(v)
+            | OptionEffect::DamageIncrease(_, v)
+            | OptionEffect::Unknown(_, v) => Some(*v),
+            OptionEffect::BypassStage(_) => None,
+        }
+    }
+
+    /// 「+10%」形式の数値部分を解析
+    fn parse_amount(amount: &str) -> Option<f32> {
+        let normalized: String = amount
+            .chars()
+            .filter(|c| !matches!(c, '%' | '％'))
+            .map(|c| match c {
+                '＋' => '+',
+                '－' => '-',
+                _ => c,
+            })
+            .collect();
+        normalized.trim().parse().ok()
+    }
+
+    /// 属性名の一文字表記を解析
+    fn parse_element(name: &str) -> Option<Element> {
+        match name {
+            "火" => Some(Element::Fire),
+            "水" => Some(Element::Water),
+            "風" => Some(Element::Wind),
+            "地" => Some(Element::Earth),
+            "光" => Some(Element::Light),
+            "闇" => Some(Element::Dark),
+            _ => None,
+        }
+    }
+}
+
+/// 装備オプションの合計値
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OptionTotals {
+    pub attack_flat: f32,
+    pub attack_percent: f32,
+    pub defense_flat: f32,
+    pub defense_percent: f32,
+    pub hp: f32,
+    pub critical_rate: f32,
+    pub critical_damage: f32,
+    pub element_attack: HashMap<Element, f32>,
+    pub all_element_attack: f32,
+    pub element_resist: HashMap<Element, f32>,
+    pub all_element_resist: f32,
+    pub defense_ignore: f32,
+    pub fixed_defense_ignore: f32,
+    pub fixed_reduction_ignore: f32,
+    pub cut_rate_pierce: f32,
+    pub damage_increase: HashMap<DamageIncreaseKind, f32>,
+    pub bypass_stages: Vec<DamageStageKind>,
+}
+
+impl OptionTotals {
+    /// オプション効果を加算
+    pub fn add(&mut self, effect: &OptionEffect) {
+        match effect {
+            OptionEffect::AttackFlat(v) => self.attack_flat += v,
+            OptionEffect::AttackPercent(v) => self.attack_percent += v,
+            OptionEffect::DefenseFlat(v) => self.defense_flat += v,
+            OptionEffect::DefensePercent(v) => self.defense_percent += v,
+            OptionEffect::Hp(v) => self.hp += v,
+            OptionEffect::CriticalRate(v) => self.critical_rate += v,
+            OptionEffect::CriticalDamage(v) => self.critical_damage += v,
+            OptionEffect::ElementAttack(element, v) => {
+                *self.element_attack.entry(*element).or_default() += v
+            }
+            OptionEffect::AllElementAttack(v) => self.all_element_attack += v,
+            OptionEffect::ElementResist(element, v) => {
+                *self.element_resist.entry(*element).or_default() += v
+            }
+            OptionEffect::AllElementResist(v) => self.all_element_resist += v,
+            OptionEffect::DefenseIgnore(v) => self.defense_ignore += v,
+            OptionEffect::FixedDefenseIgnore(v) => self.fixed_defense_ignore += v,
+            OptionEffect::FixedReductionIgnore(v) => self.fixed_reduction_ignore += v,
+            OptionEffect::CutRatePierce(v) => self.cut_rate_pierce += v,
+            OptionEffect::DamageIncrease(kind, v) => {
+                *self.damage_increase.entry(*kind).or_default() += v
+            }
+            OptionEffect::BypassStage(stage) => {
+                if !self.bypass_stages.contains(stage) {
+                    self.bypass_stages.push(*stage);
+                }
+            }
+            OptionEffect::Unknown(..) => {}
+        }
+    }
+
+    /// 指定した属性での攻撃時の属性攻撃%の合計（無属性攻撃には乗らない）
+    pub fn element_attack_percent(&self, element: Element) -> f32 {
+        if element == Element::None {
+            return 0.0;
+        }
+        self.element_attack.get(&element).copied().unwrap_or(0.0) + self.all_element_attack
+    }
+
+    /// 装備オプションによる防御貫通
+    pub fn penetration(&self) -> Penetration {
+        Penetration {
+            status_defense: self.defense_ignore,
+            fixed_defense: self.fixed_defense_ignore,
+            fixed_reduction: self.fixed_reduction_ignore,
+            cut_rate: self.cut_rate_pierce,
+        }
+    }
+
+    /// ダメージ増加%の取得
+    pub fn damage_increase_percent(&self, kind: DamageIncreaseKind) -> f32 {
+        self.damage_increase.get(&kind).copied().unwrap_or(0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_detector_options() {
+        assert_eq!(
+            OptionEffect::parse("攻撃力+10%", 10.0),
+            OptionEffect::AttackPercent(10.0)
+        );
+        assert_eq!(
+            OptionEffect::parse("攻撃力+25", 0.0),
+            OptionEffect::AttackFlat(25.0)
+        );
+        assert_eq!(
+            OptionEffect::parse("HP+100", 100.0),
+            OptionEffect::Hp(100.0)
+        );
+        assert_eq!(
+            OptionEffect::parse("クリティカル率+3%", 3.0),
+            OptionEffect::CriticalRate(3.0)
+        );
+        assert_eq!(
+            OptionEffect::parse("火属性攻撃+5%", 5.0),
+            OptionEffect::ElementAttack(Element::Fire, 5.0)
+        );
+        assert_eq!(
+            OptionEffect::parse("風属性耐性+10%", 10.0),
+            OptionEffect::ElementResist(Element::Wind, 10.0)
+        );
+        assert_eq!(
+            OptionEffect::parse("全属性攻撃+3%", 3.0),
+            OptionEffect::AllElementAttack(3.0)
+        );
+        assert_eq!(
+            OptionEffect::parse("スキルダメージ+8%", 8.0),
+            OptionEffect::DamageIncrease(DamageIncreaseKind::Skill, 8.0)
+        );
+        assert_eq!(
+            OptionEffect::parse("移動速度+5%", 5.0),
+            OptionEffect::Unknown("移動速度+5%".to_string(), 5.0)
+        );
+    }
+
+    #[test]
+    fn test_parse_penetration_options() {
+        assert_eq!(
+            OptionEffect::parse("固定防御無視+20%", 20.0),
+            OptionEffect::FixedDefenseIgnore(20.0)
+        );
+        assert_eq!(
+            OptionEffect::parse("カット率貫通+10%", 10.0),
+            OptionEffect::CutRatePierce(10.0)
+        );
+    }
+
+    #[test]
+    fn test_parse_bypass_options() {
+        assert_eq!(
+            OptionEffect::parse("固定防御無効", 0.0),
+            OptionEffect::BypassStage(DamageStageKind::FixedDefense)
+        );
+        assert!(matches!(
+            OptionEffect::parse("攻撃無効", 0.0),
+            OptionEffect::Unknown(..)
+        ));
+    }
+
+    #[test]
+    fn test_parse_uses_value_when_text_has_no_number() {
+        assert_eq!(
+            OptionEffect::parse("防御無視", 7.0),
+            OptionEffect::DefenseIgnore(7.0)
+        );
+    }
+}
@@ -0,0 +1,90 @@
+use serde::{Deserialize, Serialize};
+
+/// 防御貫通の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PenetrationKind {
+    StatusDefense,  // ステータス防御無視
+    FixedDefense,   // 固定防御無視
+    FixedReduction, // 固定減少無視
+    CutRate,        // カット率貫通
+}
+
+impl PenetrationKind {
+    /// 計算で適用する順番の全種類
+    pub const ALL: [PenetrationKind; 4] = [
+        PenetrationKind::StatusDefense,
+        PenetrationKind::FixedDefense,
+        PenetrationKind::FixedReduction,
+        PenetrationKind::CutRate,
+    ];
+}
+
+/// 防御貫通（各段階の防御値・カット率を無視する割合、%）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Penetration {
+    pub status_defense: f32,  // ステータス防御無視%
+    pub fixed_defense: f32,   // 固定防御無視%
+    pub fixed_reduction: f32, // 固定減少無視%
+    pub cut_rate: f32,        // カット率貫通%
+}
+
+impl Penetration {
+    /// 2つの貫通値を合算
+    pub fn combine(&self, other: &Penetration) -> Penetration {
+        Penetration {
+            status_defense: self.status_defense + other.status_defense,
+            fixed_defense: self.fixed_defense + other.fixed_defense,
+            fixed_reduction: self.fixed_reduction + other.fixed_reduction,
+            cut_rate: self.cut_rate + other.cut_rate,
+        }
+    }
+
+    /// 指定した種類の貫通%
+    pub fn get(&self, kind: PenetrationKind) -> f32 {
+        match kind {
+            PenetrationKind::StatusDefense => self.status_defense,
+            PenetrationKind::FixedDefense => self.fixed_defense,
+            PenetrationKind::FixedReduction => self.fixed_reduction,
+            PenetrationKind::CutRate => self.cut_rate,
+        }
+    }
+
+    /// 指定した種類の貫通%を加算
+    pub fn add(&mut self, kind: PenetrationKind, percent: f32) {
+        match kind {
+            PenetrationKind::StatusDefense => self.status_defense += percent,
+            PenetrationKind::FixedDefense => self.fixed_defense += percent,
+            PenetrationKind::FixedReduction => self.fixed_reduction += percent,
+            PenetrationKind::CutRate => self.cut_rate += percent,
+        }
+    }
+
+    /// 無視する割合 (0.0 ~ 1.0)
+    pub fn ratio(percent: f32) -> f64 {
+        (percent as f64 / 100.0).clamp(0.0, 1.0)
+    }
+
+    /// 貫通後の防御値（f64で計算して端数は切り捨て）
+    pub fn apply(value: u32, percent: f32) -> u32 {
+        (value as f64 * (1.0 - Self::ratio(percent))).floor() as u32
+    }
+
+    /// 貫通後のカット率
+    pub fn apply_cut_rate(&self, cut_rate: f32) -> f32 {
+        (cut_rate as f64 * (1.0 - Self::ratio(self.cut_rate))) as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_keeps_values_above_f32_precision() {
+        // 2^24 + 1 は f32 では表せない
+        assert_eq!(Penetration::apply(16_777_217, 0.0), 16_777_217);
+        assert_eq!(Penetration::apply(u32::MAX, 0.0), u32::MAX);
+        assert_eq!(Penetration::apply(16_777_217, 100.0), 0);
+        assert_eq!(Penetration::apply(5000, 40.0), 3000);
+    }
+}
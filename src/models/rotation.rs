@@ -0,0 +1,327 @@
+use super::buff::{Buff, BuffSet};
+use super::character::Character;
+use super::damage::DamageCalculator;
+use super::equipment::EquipmentSet;
+use super::monster::Monster;
+use super::skill::Skill;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// デフォルトの戦闘時間の上限（秒）
+pub const DEFAULT_TIME_LIMIT: f32 = 600.0;
+
+/// タイムライン上の1ヒット分の記録
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RotationEvent {
+    pub time: f32, // ヒットした時刻（秒）
+    pub skill_id: String,
+    pub skill_name: String,
+    pub hit: u32, // 何ヒット目か（1始まり）
+    #[serde(serialize_with = "crate::utils::serialize_js_number")]
+    pub damage: u64,
+    pub is_critical: bool,
+    pub is_miss: bool,
+    #[serde(serialize_with = "crate::utils::serialize_js_number")]
+    pub remaining_hp: u64,
+}
+
+/// スキル回しのシミュレーション結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RotationResult {
+    pub seed: u32,
+    pub events: Vec<RotationEvent>,
+    #[serde(serialize_with = "crate::utils::serialize_js_number")]
+    pub total_damage: u64,
+    pub kill_time: Option<f32>, // 倒した時刻（時間内に倒せなければNone）
+    pub elapsed: f32,           // シミュレーションの経過時間（秒）
+}
+
+/// スキル回しシミュレーター
+///
+/// キャラクターは手が空くたびに、優先順位の高い順にクールタイムが明けているスキルを使う。
+/// 使えるスキルがなければ最も早く明けるスキルまで待つ。
+/// スキルの動作時間は詠唱時間（0なら通常攻撃の間隔）で、全ヒットは動作の終わりに入る。
+/// クールタイムは使用開始時から数える。
+/// 計算機に設定済みのバフ・デバフは戦闘中ずっと有効なものとして、予定されたバフ・デバフに加える。
+#[derive(Debug, Clone)]
+pub struct RotationSimulator {
+    pub character: Character,
+    pub equipment: EquipmentSet,
+    pub skills: Vec<Skill>, // 優先順位の高い順
+    pub time_limit: f32,
+    pub buffs: Vec<(f32, Buff)>, // (付与する時刻, バフ・デバフ)
+}
+
+impl RotationSimulator {
+    /// 新しいシミュレーターを作成
+    ///
+    /// `priority` に含まれるIDのスキルだけを、その順番で使う。
+    /// `skills` にないIDが含まれている場合はエラー。
+    pub fn new(
+        character: Character,
+        equipment: EquipmentSet,
+        skills: &[Skill],
+        priority: &[String],
+    ) -> Result<Self> {
+        let skills = priority
+            .iter()
+            .map(|id| {
+                skills
+                    .iter()
+                    .find(|skill| &skill.id == id)
+                    .cloned()
+                    .ok_or_else(|| anyhow!("不明なスキルIDです: {}", id))
+            })
+            .collect::<Result<Vec<Skill>>>()?;
+
+        Ok(Self {
+            character,
+            equipment,
+            skills,
+            time_limit: DEFAULT_TIME_LIMIT,
+            buffs: Vec::new(),
+        })
+    }
+
+    /// 指定時刻に付与されるバフ・デバフを追加
+    pub fn with_buff(mut self, time: f32, buff: Buff) -> Self {
+        self.buffs.push((time, buff));
+        self
+    }
+
+    /// 戦闘時間の上限を設定
+    pub fn with_time_limit(mut self, time_limit: f32) -> Self {
+        self.time_limit = time_limit;
+        self
+    }
+
+    /// モンスターを倒すか時間切れになるまでシミュレート
+    pub fn run(&self, calculator: &mut DamageCalculator, monster: &Monster) -> RotationResult {
+        let mut ready_at: HashMap<&str, f32> = HashMap::new();
+        let mut events = Vec::new();
+        let mut remaining_hp = monster.hp;
+        let mut time = 0.0;
+        let mut kill_time = None;
+        let mut buff_set = BuffSet::new();
+        let mut scheduled: Vec<&(f32, Buff)> = self.buffs.iter().collect();
+        scheduled.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let mut scheduled = scheduled.into_iter().peekable();
+        // 呼び出し側で設定されたバフ・デバフは終了後に元に戻す
+        let base_buffs = calculator.buffs().to_vec();
+
+        while remaining_hp > 0 && time < self.time_limit && !self.skills.is_empty() {
+            let ready = |skill: &Skill| ready_at.get(skill.id.as_str()).copied().unwrap_or(0.0);
+
+            // 使えるスキルがなければ最も早く明けるスキルまで待つ
+            let Some(skill) = self.skills.iter().find(|skill| ready(skill) <= time) else {
+                time = self.skills.iter().map(ready).fold(f32::INFINITY, f32::min);
+                continue;
+            };
+
+            // 詠唱時間が0のスキルは通常攻撃の間隔で動ける（クールタイムは次の使用を縛るだけ）
+            let action_time = skill.action_time(self.character.attack_speed);
+            // 動作が時間内に終わらなければヒットしない
+            if time + action_time > self.time_limit {
+                time = self.time_limit;
+                break;
+            }
+
+            // 使用開始時点で有効なバフ・デバフを適用
+            while let Some((_, buff)) = scheduled.next_if(|(start, _)| *start <= time) {
+                buff_set.apply(buff.clone(), time);
+            }
+            let mut buffs = base_buffs.clone();
+            buffs.extend(buff_set.active_at(time));
+            calculator.set_buffs(buffs);
+
+            let damage_result =
+                calculator.calculate_damage(&self.character, &self.equipment, skill, monster);
+            ready_at.insert(skill.id.as_str(), time + skill.cooldown);
+            time += action_time;
+
+            for hit in 1..=damage_result.hit_count {
+                let (damage, is_critical) = calculator.roll_hit(&damage_result);
+                remaining_hp = remaining_hp.saturating_sub(damage);
+                events.push(RotationEvent {
+                    time,
+                    skill_id: skill.id.clone(),
+                    skill_name: skill.name.clone(),
+                    hit,
+                    damage,
+                    is_critical,
+                    is_miss: damage == 0,
+                    remaining_hp,
+                });
+                if remaining_hp == 0 {
+                    kill_time = Some(time);
+                    break;
+                }
+            }
+        }
+
+        calculator.set_buffs(base_buffs);
+
+        RotationResult {
+            seed: calculator.seed(),
+            total_damage: events
+                .iter()
+                .fold(0, |sum, event| sum.saturating_add(event.damage)),
+            events,
+            kill_time,
+            elapsed: time.min(self.time_limit),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::buff::BuffStat;
+    use crate::models::element::Element;
+    use crate::models::skill::{create_default_skills, DamageType};
+
+    fn setup() -> (EquipmentSet, Vec<Skill>) {
+        let equipment = EquipmentSet::test_weapon(1000, Element::None);
+
+        let skills = vec![
+            Skill::normal_attack(),
+            Skill::new("burst", "バースト", 3.0, 0.0, 2, DamageType::Physical)
+                .with_timing(5.0, 1.0),
+        ];
+
+        (equipment, skills)
+    }
+
+    #[test]
+    fn test_rotation_uses_priority_and_cooldowns() {
+        let (equipment, skills) = setup();
+        let priority = vec!["burst".to_string(), "normal_attack".to_string()];
+        let simulator =
+            RotationSimulator::new(Character::new(), equipment, &skills, &priority).unwrap();
+        let monster = Monster::new("dummy", "ダミー", 1, 20000, 0, 0, 0, 0.0, 0);
+
+        let mut calculator = DamageCalculator::with_seed(3);
+        let result = simulator.run(&mut calculator, &monster);
+
+        // t=0 にバースト（詠唱1秒、2ヒット）
+        assert_eq!(result.events[0].skill_id, "burst");
+        assert_eq!(result.events[0].time, 1.0);
+        assert_eq!(result.events[1].hit, 2);
+        // クールタイム中は通常攻撃（1秒ごと）
+        assert_eq!(result.events[2].skill_id, "normal_attack");
+        assert_eq!(result.events[2].time, 2.0);
+        // 5秒後に再びバースト
+        let second_burst = result
+            .events
+            .iter()
+            .skip(2)
+            .find(|event| event.skill_id == "burst")
+            .unwrap();
+        assert_eq!(second_burst.time, 6.0);
+
+        assert!(result.total_damage >= 20000);
+        assert_eq!(result.kill_time, Some(result.events.last().unwrap().time));
+        assert_eq!(result.events.last().unwrap().remaining_hp, 0);
+        assert_eq!(result.seed, 3);
+    }
+
+    #[test]
+    fn test_rotation_waits_for_cooldown_and_respects_time_limit() {
+        let (equipment, skills) = setup();
+        let priority = vec!["burst".to_string()];
+        let simulator = RotationSimulator::new(Character::new(), equipment, &skills, &priority)
+            .unwrap()
+            .with_time_limit(12.0);
+        let monster = Monster::new("dummy", "ダミー", 1, 1_000_000, 0, 0, 0, 0.0, 0);
+
+        let mut calculator = DamageCalculator::with_seed(3);
+        let result = simulator.run(&mut calculator, &monster);
+
+        // バーストは 0, 5, 10 秒に使用
+        let times: Vec<f32> = result
+            .events
+            .iter()
+            .filter(|event| event.hit == 1)
+            .map(|event| event.time)
+            .collect();
+        assert_eq!(times, vec![1.0, 6.0, 11.0]);
+        assert_eq!(result.kill_time, None);
+        assert_eq!(result.elapsed, 12.0);
+    }
+
+    #[test]
+    fn test_unknown_priority_id_is_an_error() {
+        let (equipment, skills) = setup();
+        let priority = vec!["burst".to_string(), "brust".to_string()];
+        let error =
+            RotationSimulator::new(Character::new(), equipment, &skills, &priority).unwrap_err();
+        assert!(error.to_string().contains("brust"));
+    }
+
+    #[test]
+    fn test_caller_buffs_are_kept_and_restored() {
+        let (equipment, skills) = setup();
+        let priority = vec!["normal_attack".to_string()];
+        let simulator = RotationSimulator::new(Character::new(), equipment, &skills, &priority)
+            .unwrap()
+            .with_time_limit(2.0)
+            .with_buff(
+                0.0,
+                Buff::new("rage", "レイジ", BuffStat::CriticalRate, 0.0, 10.0),
+            );
+        let monster = Monster::new("dummy", "ダミー", 1, 1_000_000, 0, 0, 0, 0.0, 0);
+        let might = Buff::new("might", "マイト", BuffStat::AttackPercent, 100.0, 60.0);
+
+        let mut calculator = DamageCalculator::with_seed(3).with_buffs(vec![might.clone()]);
+        let buffed = simulator.run(&mut calculator, &monster);
+        let mut plain = DamageCalculator::with_seed(3);
+        let unbuffed = simulator.run(&mut plain, &monster);
+
+        // 攻撃力+100% は予定されたバフと一緒に有効で、終了後は元のバフだけに戻る
+        assert_eq!(buffed.events.len(), unbuffed.events.len());
+        for (with_buff, without) in buffed.events.iter().zip(&unbuffed.events) {
+            assert!(with_buff.damage > without.damage);
+        }
+        assert_eq!(calculator.buffs(), [might]);
+        assert!(plain.buffs().is_empty());
+    }
+
+    #[test]
+    fn test_zero_cast_skill_does_not_lock_for_cooldown() {
+        let (equipment, _) = setup();
+        let skills = vec![
+            create_default_skills()
+                .find_by_id("fixed_strike")
+                .unwrap()
+                .clone(),
+            Skill::normal_attack(),
+        ];
+        let priority = vec!["fixed_strike".to_string(), "normal_attack".to_string()];
+        let simulator = RotationSimulator::new(Character::new(), equipment, &skills, &priority)
+            .unwrap()
+            .with_time_limit(5.0);
+        let monster = Monster::new("dummy", "ダミー", 1, 1_000_000, 0, 0, 0, 0.0, 0);
+
+        let mut calculator = DamageCalculator::with_seed(3);
+        let result = simulator.run(&mut calculator, &monster);
+
+        // 固定打は通常攻撃と同じ1秒で動作が終わり、クールタイム中は通常攻撃
+        let timeline: Vec<(&str, f32)> = result
+            .events
+            .iter()
+            .map(|event| (event.skill_id.as_str(), event.time))
+            .collect();
+        assert_eq!(
+            timeline,
+            vec![
+                ("fixed_strike", 1.0),
+                ("normal_attack", 2.0),
+                ("normal_attack", 3.0),
+                ("normal_attack", 4.0),
+                ("normal_attack", 5.0),
+            ]
+        );
+        assert!(result.events.iter().all(|event| event.time <= 5.0));
+    }
+}
@@ -0,0 +1,257 @@
+use super::character::Character;
+use super::damage::{DamageCalculator, DamageResult};
+use super::damage_category::{DamageCategory, DamageModifier};
+use super::equipment::EquipmentSet;
+use super::monster::Monster;
+use super::penetration::PenetrationKind;
+use super::skill::Skill;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+/// 感度を調べるステータス
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SensitivityStat {
+    Attack,                       // 攻撃力+N
+    CriticalRate,                 // クリティカル率+N%
+    CriticalDamage,               // クリティカルダメージ+N%
+    ElementValue,                 // 属性値+N
+    Penetration(PenetrationKind), // 防御無視・カット率貫通+N%
+    Damage(DamageCategory),       // 区分のダメージ増加+N%
+    Accuracy,                     // 命中+N
+}
+
+impl SensitivityStat {
+    /// 標準で調べる全ステータス
+    pub fn all() -> Vec<SensitivityStat> {
+        let mut stats = vec![
+            SensitivityStat::Attack,
+            SensitivityStat::CriticalRate,
+            SensitivityStat::CriticalDamage,
+            SensitivityStat::ElementValue,
+        ];
+        stats.extend(PenetrationKind::ALL.map(SensitivityStat::Penetration));
+        stats.extend(DamageCategory::ALL.map(SensitivityStat::Damage));
+        stats.push(SensitivityStat::Accuracy);
+        stats
+    }
+
+    /// ステータスを `amount` だけ上げる
+    ///
+    /// 攻撃力と属性値は武器に、貫通とダメージ増加はスキルに、それ以外はキャラクターに加算する。
+    pub fn apply(
+        self,
+        character: &mut Character,
+        equipment: &mut EquipmentSet,
+        skill: &mut Skill,
+        amount: f32,
+    ) {
+        match self {
+            SensitivityStat::Attack => {
+                let weapon = equipment.weapon_mut();
+                weapon.attack = add_amount(weapon.attack, amount);
+            }
+            SensitivityStat::CriticalRate => character.critical_rate += amount / 100.0,
+            SensitivityStat::CriticalDamage => character.critical_damage += amount / 100.0,
+            SensitivityStat::ElementValue => {
+                let weapon = equipment.weapon_mut();
+                weapon.element_value = add_amount(weapon.element_value, amount);
+            }
+            SensitivityStat::Penetration(kind) => skill.penetration.add(kind, amount),
+            SensitivityStat::Damage(category) => skill
+                .damage_modifiers
+                .push(DamageModifier::new(category, amount)),
+            SensitivityStat::Accuracy => {
+                character.accuracy = add_amount(character.accuracy, amount)
+            }
+        }
+    }
+}
+
+/// 整数のステータスに増分を加算（0未満にはしない）
+fn add_amount(value: u32, amount: f32) -> u32 {
+    (value as f64 + amount as f64).clamp(0.0, u32::MAX as f64) as u32
+}
+
+/// 1つのステータスを上げたときの変化
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatSensitivity {
+    pub stat: SensitivityStat,
+    pub amount: f32,              // 上げた量
+    pub average_damage: f64,      // 上げた後の平均ダメージ
+    pub damage_gain: f64,         // 平均ダメージの増加量
+    pub damage_gain_percent: f64, // 平均ダメージの増加率%
+    pub dps_gain: f64,            // 秒間ダメージの増加量
+    #[serde(serialize_with = "crate::utils::serialize_js_number")]
+    pub hits_to_kill: u64, // 上げた後の撃破ヒット数
+    pub time_to_kill: f32,        // 上げた後の撃破時間（秒）
+    pub time_to_kill_change: f32, // 撃破時間の変化（秒、短縮はマイナス）
+}
+
+/// ステータスごとの感度（伸びが大きい順）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SensitivityReport {
+    pub base: DamageResult,          // 現在の計算結果
+    pub stats: Vec<StatSensitivity>, // 平均ダメージの伸びが大きい順、同じなら撃破時間の短縮が大きい順
+}
+
+impl SensitivityReport {
+    /// 各ステータスを1ずつ上げたときの感度
+    pub fn analyze(
+        calculator: &mut DamageCalculator,
+        character: &Character,
+        equipment: &EquipmentSet,
+        skill: &Skill,
+        monster: &Monster,
+    ) -> Self {
+        let increments: Vec<(SensitivityStat, f32)> = SensitivityStat::all()
+            .into_iter()
+            .map(|stat| (stat, 1.0))
+            .collect();
+        Self::analyze_increments(
+            calculator,
+            character,
+            equipment,
+            skill,
+            monster,
+            &increments,
+        )
+    }
+
+    /// ステータスと増分を指定して感度を計算
+    ///
+    /// 最低ダメージや切り捨てがあるため、増分が小さいと伸びが0になることがある。
+    /// 実際に狙う強化幅（例: 攻撃力+100）で比べたいときは増分を指定する。
+    pub fn analyze_increments(
+        calculator: &mut DamageCalculator,
+        character: &Character,
+        equipment: &EquipmentSet,
+        skill: &Skill,
+        monster: &Monster,
+        increments: &[(SensitivityStat, f32)],
+    ) -> Self {
+        let base = calculator.calculate_damage(character, equipment, skill, monster);
+
+        let mut stats: Vec<StatSensitivity> = increments
+            .iter()
+            .map(|&(stat, amount)| {
+                let mut character = character.clone();
+                let mut equipment = equipment.clone();
+                let mut skill = skill.clone();
+                stat.apply(&mut character, &mut equipment, &mut skill, amount);
+                let result = calculator.calculate_damage(&character, &equipment, &skill, monster);
+
+                let damage_gain = result.average_damage - base.average_damage;
+                let damage_gain_percent = if base.average_damage > 0.0 {
+                    damage_gain / base.average_damage * 100.0
+                } else {
+                    0.0
+                };
+                StatSensitivity {
+                    stat,
+                    amount,
+                    average_damage: result.average_damage,
+                    damage_gain,
+                    damage_gain_percent,
+                    dps_gain: result.dps - base.dps,
+                    hits_to_kill: result.hits_to_kill,
+                    time_to_kill: result.time_to_kill,
+                    time_to_kill_change: result.time_to_kill - base.time_to_kill,
+                }
+            })
+            .collect();
+
+        stats.sort_by(|a, b| {
+            b.damage_gain
+                .partial_cmp(&a.damage_gain)
+                .unwrap_or(Ordering::Equal)
+                .then(
+                    a.time_to_kill_change
+                        .partial_cmp(&b.time_to_kill_change)
+                        .unwrap_or(Ordering::Equal),
+                )
+        });
+
+        Self { base, stats }
+    }
+
+    /// ステータスの感度を取得
+    pub fn get(&self, stat: SensitivityStat) -> Option<&StatSensitivity> {
+        self.stats
+            .iter()
+            .find(|sensitivity| sensitivity.stat == stat)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::element::Element;
+
+    fn setup(attack: u32) -> (Character, EquipmentSet) {
+        let equipment = EquipmentSet::test_weapon(attack, Element::Fire);
+        (Character::new().with_critical(0.1, 1.5), equipment)
+    }
+
+    #[test]
+    fn test_fixed_defense_floor_makes_attack_worthless() {
+        // 固定防御が攻撃力を上回っていると、攻撃力+1では最低ダメージ1のまま
+        let monster = Monster::new("boss", "ボス", 40, 100000, 0, 5000, 0, 0.0, 0);
+        let (character, equipment) = setup(4990);
+        let mut calculator = DamageCalculator::with_seed(1);
+        let report = SensitivityReport::analyze(
+            &mut calculator,
+            &character,
+            &equipment,
+            &Skill::normal_attack(),
+            &monster,
+        );
+
+        assert_eq!(
+            report.get(SensitivityStat::Attack).unwrap().damage_gain,
+            0.0
+        );
+        let first = &report.stats[0];
+        assert_eq!(
+            first.stat,
+            SensitivityStat::Penetration(PenetrationKind::FixedDefense)
+        );
+        assert!(first.damage_gain > 0.0);
+        assert!(report
+            .stats
+            .windows(2)
+            .all(|pair| pair[0].damage_gain >= pair[1].damage_gain));
+    }
+
+    #[test]
+    fn test_increments_without_defense() {
+        let monster = Monster::new("dummy", "ダミー", 1, 100000, 0, 0, 0, 0.0, 0);
+        let (character, equipment) = setup(1000);
+        let mut calculator = DamageCalculator::with_seed(1);
+        let report = SensitivityReport::analyze_increments(
+            &mut calculator,
+            &character,
+            &equipment,
+            &Skill::normal_attack(),
+            &monster,
+            &[
+                (SensitivityStat::Attack, 100.0),
+                (
+                    SensitivityStat::Damage(DamageCategory::DamageIncrease),
+                    10.0,
+                ),
+                (SensitivityStat::Accuracy, 10.0),
+            ],
+        );
+
+        // 攻撃力+100 と与ダメージ+10% はどちらも 1000 → 1100 で同じ伸び
+        let attack = report.get(SensitivityStat::Attack).unwrap();
+        let damage = report
+            .get(SensitivityStat::Damage(DamageCategory::DamageIncrease))
+            .unwrap();
+        assert!((attack.damage_gain_percent - 10.0).abs() < 0.1);
+        assert!((damage.damage_gain_percent - 10.0).abs() < 0.1);
+        // 回避のないモンスターには命中は効かない
+        assert_eq!(report.stats[2].stat, SensitivityStat::Accuracy);
+        assert_eq!(report.stats[2].damage_gain, 0.0);
+    }
+}
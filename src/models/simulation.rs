@@ -0,0 +1,216 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// ヒストグラムの区間数
+pub const HISTOGRAM_BINS: u32 = 20;
+/// 1回のシミュレーションで抽選するヒット数の上限（パーセンタイルのために全ダメージを保持するため）
+pub const MAX_SIMULATED_HITS: u32 = 1_000_000;
+
+/// ダメージ分布のヒストグラムの1区間
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistogramBin {
+    #[serde(serialize_with = "crate::utils::serialize_js_number")]
+    pub lower: u64, // 区間の下限（含む）
+    #[serde(serialize_with = "crate::utils::serialize_js_number")]
+    pub upper: u64, // 区間の上限（含む）
+    pub count: u32,
+}
+
+/// 撃破に要したヒット数ごとの回数
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HitsToKillCount {
+    pub hits: u32,
+    pub count: u32,
+}
+
+/// 複数回攻撃のシミュレーション結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulationResult {
+    pub seed: u32, // 使用した乱数シード
+    pub hits: u32,
+    #[serde(serialize_with = "crate::utils::serialize_js_number")]
+    pub total_damage: u64, // u64 の範囲に飽和
+    pub average_damage: f64,
+    pub std_dev: f64, // 1ヒットのダメージの標準偏差
+    #[serde(serialize_with = "crate::utils::serialize_js_number")]
+    pub min_damage: u64,
+    #[serde(serialize_with = "crate::utils::serialize_js_number")]
+    pub max_damage: u64,
+    #[serde(serialize_with = "crate::utils::serialize_js_number")]
+    pub p5: u64,
+    #[serde(serialize_with = "crate::utils::serialize_js_number")]
+    pub p50: u64,
+    #[serde(serialize_with = "crate::utils::serialize_js_number")]
+    pub p95: u64,
+    pub critical_count: u32,
+    #[serde(default)]
+    pub miss_count: u32, // 外れた回数（ダメージ0のヒット）
+    pub histogram: Vec<HistogramBin>,
+    pub kills: u32, // シミュレーション中に倒せた回数
+    pub hits_to_kill: Vec<HitsToKillCount>,
+}
+
+impl SimulationResult {
+    /// 1ヒットごとのダメージ列から結果を集計
+    ///
+    /// 撃破までのヒット数は、ダメージ列を先頭から順にHPに達するまで区切って数える。
+    /// 最後の倒しきれなかった分は含めない。
+    pub fn from_samples(seed: u32, samples: &[u64], critical_count: u32, monster_hp: u64) -> Self {
+        let hits = samples.len() as u32;
+        let total_damage = samples.iter().fold(0u64, |sum, &d| sum.saturating_add(d));
+        // 合計が飽和しても平均が狂わないよう、平均と分散は f64 で求める
+        let average_damage = if hits > 0 {
+            samples.iter().map(|&d| d as f64).sum::<f64>() / hits as f64
+        } else {
+            0.0
+        };
+        let variance = if hits > 0 {
+            samples
+                .iter()
+                .map(|&d| (d as f64 - average_damage).powi(2))
+                .sum::<f64>()
+                / hits as f64
+        } else {
+            0.0
+        };
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+
+        Self {
+            seed,
+            hits,
+            total_damage,
+            average_damage,
+            std_dev: variance.sqrt(),
+            min_damage: sorted.first().copied().unwrap_or(0),
+            max_damage: sorted.last().copied().unwrap_or(0),
+            p5: percentile(&sorted, 5.0),
+            p50: percentile(&sorted, 50.0),
+            p95: percentile(&sorted, 95.0),
+            critical_count,
+            miss_count: samples.iter().filter(|&&d| d == 0).count() as u32,
+            histogram: histogram(&sorted, HISTOGRAM_BINS),
+            kills: 0,
+            hits_to_kill: Vec::new(),
+        }
+        .with_kills(samples, monster_hp)
+    }
+
+    /// 撃破までのヒット数の分布を集計
+    fn with_kills(mut self, samples: &[u64], monster_hp: u64) -> Self {
+        let mut counts: BTreeMap<u32, u32> = BTreeMap::new();
+        let mut damage: u64 = 0;
+        let mut hits = 0;
+
+        for &sample in samples {
+            damage = damage.saturating_add(sample);
+            hits += 1;
+            if damage >= monster_hp {
+                *counts.entry(hits).or_default() += 1;
+                damage = 0;
+                hits = 0;
+            }
+        }
+
+        self.kills = counts.values().sum();
+        self.hits_to_kill = counts
+            .into_iter()
+            .map(|(hits, count)| HitsToKillCount { hits, count })
+            .collect();
+        self
+    }
+}
+
+/// ソート済みの値から最近傍順位法でパーセンタイルを求める
+pub fn percentile(sorted: &[u64], p: f32) -> u64 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let rank = ((p / 100.0) * sorted.len() as f32).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// ソート済みの値を等幅の区間に分けて数える
+pub fn histogram(sorted: &[u64], bins: u32) -> Vec<HistogramBin> {
+    let (Some(&min), Some(&max)) = (sorted.first(), sorted.last()) else {
+        return Vec::new();
+    };
+
+    let width = ((max - min) / bins as u64).saturating_add(1);
+    let bin_count = (max - min) / width + 1;
+    let mut result: Vec<HistogramBin> = (0..bin_count)
+        .map(|i| HistogramBin {
+            lower: min + i * width,
+            upper: (min + i * width).saturating_add(width - 1),
+            count: 0,
+        })
+        .collect();
+
+    for &value in sorted {
+        result[((value - min) / width) as usize].count += 1;
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentile_nearest_rank() {
+        let sorted: Vec<u64> = (1..=100).collect();
+        assert_eq!(percentile(&sorted, 5.0), 5);
+        assert_eq!(percentile(&sorted, 50.0), 50);
+        assert_eq!(percentile(&sorted, 95.0), 95);
+        assert_eq!(percentile(&[], 50.0), 0);
+    }
+
+    #[test]
+    fn test_histogram_counts_every_sample() {
+        let sorted: Vec<u64> = (900..=1100).collect();
+        let bins = histogram(&sorted, HISTOGRAM_BINS);
+
+        assert!(bins.len() as u32 <= HISTOGRAM_BINS);
+        assert_eq!(bins.first().unwrap().lower, 900);
+        assert!(bins.last().unwrap().upper >= 1100);
+        assert_eq!(bins.iter().map(|b| b.count).sum::<u32>(), 201);
+    }
+
+    #[test]
+    fn test_hits_to_kill_distribution() {
+        // HP 250: [100, 100, 100] → 3ヒット、[200, 100] → 2ヒット、[300] → 1ヒット
+        let samples = [100, 100, 100, 200, 100, 300, 50];
+        let result = SimulationResult::from_samples(1, &samples, 0, 250);
+
+        assert_eq!(result.kills, 3);
+        assert_eq!(result.miss_count, 0);
+        assert_eq!(
+            result.hits_to_kill,
+            vec![
+                HitsToKillCount { hits: 1, count: 1 },
+                HitsToKillCount { hits: 2, count: 1 },
+                HitsToKillCount { hits: 3, count: 1 },
+            ]
+        );
+        assert_eq!(result.min_damage, 50);
+        assert_eq!(result.max_damage, 300);
+    }
+
+    #[test]
+    fn test_extreme_samples_saturate() {
+        let samples = [u64::MAX, u64::MAX, 0];
+        let result = SimulationResult::from_samples(1, &samples, 0, u64::MAX);
+
+        assert_eq!(result.total_damage, u64::MAX);
+        assert!(
+            (result.average_damage - u64::MAX as f64 * 2.0 / 3.0).abs() / result.average_damage
+                < 1e-9
+        );
+        assert_eq!(result.max_damage, u64::MAX);
+        assert_eq!(result.miss_count, 1);
+        assert_eq!(result.kills, 2);
+        assert_eq!(result.histogram.iter().map(|b| b.count).sum::<u32>(), 3);
+    }
+}
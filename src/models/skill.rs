@@ -0,0 +1,231 @@
+use super::damage::DamageStageKind;
+use super::damage_category::DamageModifier;
+use super::element::Element;
+use super::penetration::Penetration;
+use serde::{Deserialize, Serialize};
+
+/// ダメージの種類
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DamageType {
+    #[default]
+    Physical, // 物理
+    Magic, // 魔法
+    Fixed, // 固定ダメージ
+    True,  // 防御を無視する真ダメージ（物理攻撃力で計算し、カット率以降は適用）
+}
+
+/// スキル
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Skill {
+    pub id: String,
+    pub name: String,
+    pub level: u32,
+    pub base_multiplier: f32,      // レベル1のダメージ倍率
+    pub multiplier_per_level: f32, // レベル1上昇ごとの倍率増加
+    pub hit_count: u32,            // 1回の使用でのヒット数
+    pub damage_type: DamageType,
+    pub fixed_damage: u32,        // 固定ダメージスキルの1ヒットのダメージ
+    pub element: Option<Element>, // 属性の上書き（Noneなら武器の属性）
+    pub cooldown: f32,            // クールタイム（秒）
+    pub cast_time: f32,           // 詠唱時間（秒）
+    #[serde(default)]
+    pub penetration: Penetration, // スキル固有の防御貫通
+    #[serde(default)]
+    pub damage_modifiers: Vec<DamageModifier>, // スキル固有のダメージ補正
+    #[serde(default)]
+    pub bypass_stages: Vec<DamageStageKind>, // 計算を飛ばす段階
+}
+
+impl Skill {
+    /// 新しいスキルを作成
+    pub fn new(
+        id: impl Into<String>,
+        name: impl Into<String>,
+        base_multiplier: f32,
+        multiplier_per_level: f32,
+        hit_count: u32,
+        damage_type: DamageType,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+            level: 1,
+            base_multiplier,
+            multiplier_per_level,
+            hit_count,
+            damage_type,
+            fixed_damage: 0,
+            element: None,
+            cooldown: 0.0,
+            cast_time: 0.0,
+            penetration: Penetration::default(),
+            damage_modifiers: Vec::new(),
+            bypass_stages: Vec::new(),
+        }
+    }
+
+    /// 通常攻撃
+    pub fn normal_attack() -> Self {
+        Self::new(
+            "normal_attack",
+            "通常攻撃",
+            1.0,
+            0.0,
+            1,
+            DamageType::Physical,
+        )
+    }
+
+    /// スキルレベルを設定
+    pub fn with_level(mut self, level: u32) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// 属性を上書き
+    pub fn with_element(mut self, element: Element) -> Self {
+        self.element = Some(element);
+        self
+    }
+
+    /// 固定ダメージを設定
+    pub fn with_fixed_damage(mut self, fixed_damage: u32) -> Self {
+        self.fixed_damage = fixed_damage;
+        self
+    }
+
+    /// 防御貫通を設定
+    pub fn with_penetration(mut self, penetration: Penetration) -> Self {
+        self.penetration = penetration;
+        self
+    }
+
+    /// ダメージ補正を追加
+    pub fn with_damage_modifier(mut self, modifier: DamageModifier) -> Self {
+        self.damage_modifiers.push(modifier);
+        self
+    }
+
+    /// 計算を飛ばす段階を追加
+    pub fn with_bypass(mut self, stage: DamageStageKind) -> Self {
+        self.bypass_stages.push(stage);
+        self
+    }
+
+    /// クールタイムと詠唱時間を設定
+    pub fn with_timing(mut self, cooldown: f32, cast_time: f32) -> Self {
+        self.cooldown = cooldown;
+        self.cast_time = cast_time;
+        self
+    }
+
+    /// 通常攻撃かどうか
+    pub fn is_normal_attack(&self) -> bool {
+        self.id == "normal_attack"
+    }
+
+    /// 1回の使用にかかる時間（秒）
+    ///
+    /// 通常攻撃は攻撃速度（1秒あたりの攻撃回数）から、スキルは動作時間とクールタイムの長い方から求める。
+    /// スキル回しと同じく、詠唱時間が0のスキルは通常攻撃と同じ間隔で動けるものとする。
+    pub fn cycle_time(&self, attack_speed: f32) -> f32 {
+        let action_time = self.action_time(attack_speed);
+        if self.is_normal_attack() {
+            return action_time;
+        }
+        action_time.max(self.cooldown)
+    }
+
+    /// 1回の使用で動けなくなる時間（秒）
+    ///
+    /// 詠唱時間があればその間、なければ通常攻撃と同じ間隔。クールタイムは次の使用を縛るだけ。
+    pub fn action_time(&self, attack_speed: f32) -> f32 {
+        if self.cast_time > 0.0 {
+            self.cast_time
+        } else {
+            1.0 / attack_speed.max(f32::EPSILON)
+        }
+    }
+
+    /// 現在のスキルレベルでのダメージ倍率
+    pub fn multiplier(&self) -> f32 {
+        self.base_multiplier + self.multiplier_per_level * self.level.saturating_sub(1) as f32
+    }
+}
+
+impl Default for Skill {
+    fn default() -> Self {
+        Self::normal_attack()
+    }
+}
+
+/// スキルのデータベース
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SkillDatabase {
+    pub skills: Vec<Skill>,
+}
+
+impl SkillDatabase {
+    /// 新しいスキルデータベースを作成
+    pub fn new() -> Self {
+        Self { skills: Vec::new() }
+    }
+
+    /// スキルを追加
+    pub fn add_skill(&mut self, skill: Skill) {
+        self.skills.push(skill);
+    }
+
+    /// IDでスキルを検索
+    pub fn find_by_id(&self, id: &str) -> Option<&Skill> {
+        self.skills.iter().find(|s| s.id == id)
+    }
+}
+
+/// デフォルトのスキルデータを生成
+pub fn create_default_skills() -> SkillDatabase {
+    let mut db = SkillDatabase::new();
+
+    db.add_skill(Skill::normal_attack());
+
+    // サンプルスキル（数値は仮）
+    db.add_skill(
+        Skill::new(
+            "multi_slash",
+            "連続斬り",
+            1.2,  // レベル1の倍率
+            0.05, // レベルごとの倍率増加
+            3,    // ヒット数
+            DamageType::Physical,
+        )
+        .with_timing(4.0, 0.5),
+    );
+
+    db.add_skill(
+        Skill::new(
+            "fire_bolt",
+            "ファイアボルト",
+            2.0, // レベル1の倍率
+            0.1, // レベルごとの倍率増加
+            1,   // ヒット数
+            DamageType::Magic,
+        )
+        .with_element(Element::Fire)
+        .with_timing(2.0, 1.0),
+    );
+
+    db.add_skill(
+        Skill::new(
+            "fixed_strike",
+            "固定打",
+            1.0, // 倍率（固定ダメージでは未使用）
+            0.0, // レベルごとの倍率増加
+            1,   // ヒット数
+            DamageType::Fixed,
+        )
+        .with_fixed_damage(5000)
+        .with_timing(10.0, 0.0),
+    );
+
+    db
+}
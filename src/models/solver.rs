@@ -0,0 +1,253 @@
+use super::character::Character;
+use super::damage::{DamageCalculator, DamageResult};
+use super::equipment::EquipmentSet;
+use super::monster::Monster;
+use super::penetration::PenetrationKind;
+use super::skill::Skill;
+use serde::{Deserialize, Serialize};
+
+/// 貫通%を探索する刻み（0.01%単位）
+const PENETRATION_STEPS_PER_PERCENT: u64 = 100;
+
+/// 逆算の目標
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum SolveTarget {
+    DamagePerHit(u64), // 1ヒットの最小ダメージがこの値以上（確定で与えられるダメージ）
+    HitsToKill(u64),   // 平均ダメージでこのヒット数以内に倒す
+    TimeToKill(f32),   // 平均ダメージでこの秒数以内に倒す
+}
+
+impl SolveTarget {
+    /// 計算結果が目標を満たしているか
+    pub fn is_met(&self, result: &DamageResult) -> bool {
+        match *self {
+            SolveTarget::DamagePerHit(damage) => result.min_damage >= damage,
+            SolveTarget::HitsToKill(hits) => result.hits_to_kill <= hits,
+            SolveTarget::TimeToKill(seconds) => result.time_to_kill <= seconds,
+        }
+    }
+}
+
+/// 逆算する値
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SolveFor {
+    Attack,                       // 攻撃力（武器の攻撃力を増やす）
+    Penetration(PenetrationKind), // 防御貫通%（スキルの貫通を増やす）
+    ElementValue,                 // 属性値（武器の属性値を増やす）
+}
+
+/// 逆算の結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Solution {
+    pub solve_for: SolveFor,
+    /// 目標を満たす最小値
+    ///
+    /// 攻撃力はバフ込みの最終的な攻撃力、貫通は装備とスキルの合計%、属性値は攻撃属性の合計値。
+    pub value: f64,
+    pub additional: f64, // 現在の値からの不足分
+    pub result: DamageResult,
+}
+
+/// 目標のダメージ・ヒット数・撃破時間に必要な最小値を逆算する
+///
+/// ダメージ計算は各段階の最低ダメージや切り捨てを含む階段状の関数だが、
+/// 攻撃力・貫通・属性値のどれについても単調非減少なので、
+/// 整数の刻みで二分探索して目標を満たす最小値を求める。
+#[derive(Debug, Clone)]
+pub struct RequirementSolver {
+    pub character: Character,
+    pub equipment: EquipmentSet,
+    pub skill: Skill,
+}
+
+impl RequirementSolver {
+    /// 新しいソルバーを作成（逆算する値以外はこの入力で固定）
+    pub fn new(character: Character, equipment: EquipmentSet, skill: Skill) -> Self {
+        Self {
+            character,
+            equipment,
+            skill,
+        }
+    }
+
+    /// 目標を満たす最小値を求める（上限まで上げても満たせなければNone）
+    pub fn solve(
+        &self,
+        calculator: &mut DamageCalculator,
+        monster: &Monster,
+        target: SolveTarget,
+        solve_for: SolveFor,
+    ) -> Option<Solution> {
+        let weapon = self.equipment.weapon.as_ref();
+        let upper = match solve_for {
+            SolveFor::Attack => u32::MAX as u64 - weapon.map_or(0, |w| w.attack) as u64,
+            SolveFor::Penetration(kind) => {
+                let current = self.total_penetration(kind).clamp(0.0, 100.0);
+                ((100.0 - current) as f64 * PENETRATION_STEPS_PER_PERCENT as f64).ceil() as u64
+            }
+            SolveFor::ElementValue => {
+                u32::MAX as u64 - weapon.map_or(0, |w| w.element_value) as u64
+            }
+        };
+
+        let mut evaluate = |step: u64| {
+            let (equipment, skill) = self.with_step(solve_for, step);
+            calculator.calculate_damage(&self.character, &equipment, &skill, monster)
+        };
+
+        let step = minimum_step(upper, |step| target.is_met(&evaluate(step)))?;
+        let result = evaluate(step);
+        let (equipment, _) = self.with_step(solve_for, step);
+
+        let (value, additional) = match solve_for {
+            SolveFor::Attack => (result.attack as f64, step as f64),
+            SolveFor::Penetration(kind) => {
+                let additional = step as f64 / PENETRATION_STEPS_PER_PERCENT as f64;
+                (self.total_penetration(kind) as f64 + additional, additional)
+            }
+            SolveFor::ElementValue => {
+                let element = self.skill.element.unwrap_or(equipment.element());
+                let value = equipment.stat_totals().element_value(element);
+                (value as f64, step as f64)
+            }
+        };
+
+        Some(Solution {
+            solve_for,
+            value,
+            additional,
+            result,
+        })
+    }
+
+    /// 装備とスキルによる現在の貫通%
+    fn total_penetration(&self, kind: PenetrationKind) -> f32 {
+        self.equipment.option_totals().penetration().get(kind) + self.skill.penetration.get(kind)
+    }
+
+    /// 逆算する値を `step` だけ増やした装備とスキル
+    ///
+    /// 攻撃力と属性値は武器に加算するので、スキルで武器と違う属性に上書きしている場合は属性値では伸びない。
+    fn with_step(&self, solve_for: SolveFor, step: u64) -> (EquipmentSet, Skill) {
+        let mut equipment = self.equipment.clone();
+        let mut skill = self.skill.clone();
+        let weapon = equipment.weapon_mut();
+
+        match solve_for {
+            SolveFor::Attack => weapon.attack = weapon.attack.saturating_add(step as u32),
+            SolveFor::Penetration(kind) => skill
+                .penetration
+                .add(kind, step as f32 / PENETRATION_STEPS_PER_PERCENT as f32),
+            SolveFor::ElementValue => {
+                weapon.element_value = weapon.element_value.saturating_add(step as u32)
+            }
+        }
+
+        (equipment, skill)
+    }
+}
+
+/// `0..=upper` で `is_met` を満たす最小の値を二分探索する（単調性を前提とする）
+fn minimum_step(upper: u64, mut is_met: impl FnMut(u64) -> bool) -> Option<u64> {
+    if !is_met(upper) {
+        return None;
+    }
+
+    let (mut low, mut high) = (0, upper);
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if is_met(mid) {
+            high = mid;
+        } else {
+            low = mid + 1;
+        }
+    }
+    Some(low)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::element::Element;
+
+    fn solver_with_attack(attack: u32) -> RequirementSolver {
+        let equipment = EquipmentSet::test_weapon(attack, Element::Fire);
+        RequirementSolver::new(Character::new(), equipment, Skill::normal_attack())
+    }
+
+    fn boss() -> Monster {
+        Monster::new("boss", "ボス", 40, 100000, 0, 5000, 0, 0.0, 0)
+    }
+
+    #[test]
+    fn test_minimum_attack_for_one_shot_is_exact() {
+        let monster = boss();
+        let mut calculator = DamageCalculator::with_seed(1);
+        let solver = solver_with_attack(1000);
+
+        let target = SolveTarget::DamagePerHit(monster.hp);
+        let solution = solver
+            .solve(&mut calculator, &monster, target, SolveFor::Attack)
+            .unwrap();
+        assert!(target.is_met(&solution.result));
+
+        // 1つ少ない攻撃力では届かない（最低ダメージ1の平坦な区間も正しく越える）
+        let below = solver_with_attack(1000 + solution.additional as u32 - 1);
+        let result =
+            calculator.calculate_damage(&below.character, &below.equipment, &below.skill, &monster);
+        assert!(!target.is_met(&result));
+
+        // ヒット数の目標: 10ヒット以内なら1ヒットの平均ダメージ10000以上
+        let solution = solver
+            .solve(
+                &mut calculator,
+                &monster,
+                SolveTarget::HitsToKill(10),
+                SolveFor::Attack,
+            )
+            .unwrap();
+        assert_eq!(solution.result.hits_to_kill, 10);
+    }
+
+    #[test]
+    fn test_minimum_penetration_and_element_value() {
+        let monster = boss();
+        let mut calculator = DamageCalculator::with_seed(1);
+        let solver = solver_with_attack(6000);
+
+        // 最小ダメージ 0.9 × (6000 - 5000 × (1 - p)) ≥ 2700 となる最小の p は 40%
+        let solution = solver
+            .solve(
+                &mut calculator,
+                &monster,
+                SolveTarget::DamagePerHit(2700),
+                SolveFor::Penetration(PenetrationKind::FixedDefense),
+            )
+            .unwrap();
+        assert!((solution.value - 40.0).abs() < 0.02);
+
+        // 属性値 v で 1000 × (1 + v / 100) の最小ダメージ 900 × (1 + v / 100) ≥ 1800 → v = 100
+        let solution = solver
+            .solve(
+                &mut calculator,
+                &monster,
+                SolveTarget::DamagePerHit(1800),
+                SolveFor::ElementValue,
+            )
+            .unwrap();
+        assert_eq!(solution.value, 100.0);
+    }
+
+    #[test]
+    fn test_unreachable_target() {
+        let monster = Monster::new("wall", "壁", 1, 100, 0, 0, 0, 1.0, 0);
+        let mut calculator = DamageCalculator::with_seed(1);
+        let solution = solver_with_attack(1000).solve(
+            &mut calculator,
+            &monster,
+            SolveTarget::HitsToKill(1),
+            SolveFor::Penetration(PenetrationKind::FixedDefense),
+        );
+        assert!(solution.is_none());
+    }
+}
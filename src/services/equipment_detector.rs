@@ -1,4 +1,5 @@
 use super::image_processor::ImageProcessor;
+use crate::models::element::Element;
 use crate::models::equipment::{Equipment, EquipmentOption, EquipmentSet, EquipmentType};
 use anyhow::Result;
 use web_sys::HtmlImageElement;
@@ -29,6 +30,7 @@ impl EquipmentDetector {
             attack: 120,
             defense: 0,
             element_value: 15,
+            element: Element::None,
             options: vec![
                 EquipmentOption {
                     name: "攻撃力+10%".to_string(),
@@ -47,6 +49,7 @@ impl EquipmentDetector {
             attack: 0,
             defense: 80,
             element_value: 0,
+            element: Element::None,
             options: vec![
                 EquipmentOption {
                     name: "防御力+15%".to_string(),
@@ -65,6 +68,7 @@ impl EquipmentDetector {
             attack: 5,
             defense: 5,
             element_value: 10,
+            element: Element::Fire,
             options: vec![EquipmentOption {
                 name: "火属性攻撃+5%".to_string(),
                 value: 5.0,
@@ -77,6 +81,7 @@ impl EquipmentDetector {
             attack: 0,
             defense: 10,
             element_value: 10,
+            element: Element::Wind,
             options: vec![EquipmentOption {
                 name: "風属性耐性+10%".to_string(),
                 value: 10.0,
@@ -89,6 +94,7 @@ impl EquipmentDetector {
             attack: 20,
             defense: 20,
             element_value: 20,
+            element: Element::None,
             options: vec![
                 EquipmentOption {
                     name: "全属性攻撃+3%".to_string(),
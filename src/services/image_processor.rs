@@ -37,8 +37,8 @@ impl ImageProcessor {
 
     /// HTMLImageElementから画像データを取得
     pub fn get_image_data(&self, img: &HtmlImageElement) -> Result<ImageBuffer<Rgba<u8>, Vec<u8>>> {
-        let width = img.width() as u32;
-        let height = img.height() as u32;
+        let width = img.width();
+        let height = img.height();
 
         // キャンバスを作成
         let window = web_sys::window().ok_or_else(|| anyhow!("No window found"))?;
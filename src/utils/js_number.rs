@@ -0,0 +1,13 @@
+use serde::Serializer;
+
+/// JavaScriptの数値で正確に表せる最大の整数（Number.MAX_SAFE_INTEGER）
+pub const MAX_SAFE_INTEGER: u64 = (1 << 53) - 1;
+
+/// u64 をJavaScriptの数値として扱える範囲に丸めてシリアライズ
+///
+/// serde-wasm-bindgen は 2^53 以上の u64 を数値に変換できずエラーになるため、
+/// 飽和したダメージやHPは `MAX_SAFE_INTEGER` として返す。
+/// `#[serde(serialize_with = "crate::utils::serialize_js_number")]` で使う。
+pub fn serialize_js_number<S: Serializer>(value: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u64((*value).min(MAX_SAFE_INTEGER))
+}
@@ -1,5 +1,7 @@
 mod format;
+mod js_number;
 mod storage;
 
 pub use format::*;
+pub use js_number::*;
 pub use storage::*;